@@ -1,12 +1,14 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, to_json_binary};
-use cw2::set_contract_version;
+use cosmwasm_std::{Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsgResult, to_json_binary};
+use cw2::{get_contract_version, set_contract_version};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, MigrateMsg};
-use crate::execute::{bidding, collection_bidding, listing};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, MigrateMsg, SudoMsg};
+use crate::execute::{admin, balance, bidding, blocklist, bundle, claims, collection_bidding, collections, cw20, denoms, fees, hooks, listing, royalties, signed_order, sudo, swap, token_set_bidding, trait_bidding};
+use crate::migration::{self, parse_version, run_migration_steps, save_migration_progress, DEFAULT_MIGRATION_LIMIT};
 use crate::query;
+use crate::state::{Config, MigrationProgress, CONFIG, DEFAULT_HOOK_GAS_LIMIT, MIGRATION_PROGRESS};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:tradesei";
@@ -17,23 +19,99 @@ pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(admin.as_str())
+            .map_err(|_e| ContractError::InvalidAdmin {  })?,
+        None => info.sender.clone(),
+    };
+    CONFIG.save(deps.storage, &Config {
+        admin: admin.clone(),
+        referral_fee_share: Decimal::zero(),
+        curation_mode: false,
+        resale_cooldown_seconds: 0,
+        pull_payouts: vec![],
+        hook_gas_limit: DEFAULT_HOOK_GAS_LIMIT,
+    })?;
+
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("owner", info.sender)
+        .add_attribute("admin", admin)
     )
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::MigrationContractMismatch {  });
+    }
+    if parse_version(stored.version.as_str())? > parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::MigrationDowngrade {  });
+    }
+    // an unfinished paginated migration is resumed from its stored cursor, e.g. by new code fixing a failing step
+    let progress = MIGRATION_PROGRESS.may_load(deps.storage)?;
+
+    let mut response = Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", progress.as_ref().map_or(stored.version.clone(), |progress| progress.from_version.clone()))
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("resumed_migration", progress.is_some().to_string());
+
+    // versions before config was introduced get their admin here
+    if let Some(admin) = msg.admin {
+        let admin = deps.api.addr_validate(admin.as_str())
+            .map_err(|_e| ContractError::InvalidAdmin {  })?;
+        let config = match CONFIG.may_load(deps.storage)? {
+            Some(config) => Config { admin: admin.clone(), ..config },
+            None => Config { admin: admin.clone(), referral_fee_share: Decimal::zero(), curation_mode: false, resale_cooldown_seconds: 0, pull_payouts: vec![], hook_gas_limit: DEFAULT_HOOK_GAS_LIMIT },
+        };
+        CONFIG.save(deps.storage, &config)?;
+        response = response.add_attribute("admin", admin);
+    }
+
+    let progress = progress.unwrap_or(MigrationProgress {
+        from_version: stored.version,
+        step: 0,
+        start_after: None,
+        migrated: 0,
+        clearing_migrated_keys: false,
+    });
+    let mut events = vec![];
+    let progress = run_migration_steps(deps.storage, progress, msg.limit.unwrap_or(DEFAULT_MIGRATION_LIMIT), &mut events)?;
+    save_migration_progress(deps.storage, &progress)?;
+    response = response
+        .add_events(events)
+        .add_attribute("migration_finished", progress.is_none().to_string());
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(response)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
     _deps: DepsMut,
     _env: Env,
-    _msg: MigrateMsg,
+    msg: Reply,
 ) -> Result<Response, ContractError> {
-    // No state migration needed, but the function must exist
-    Ok(Response::new().add_attribute("method", "migrate"))
+    match (msg.id, msg.result) {
+        // hook failures are only logged, the trade itself stays
+        (hooks::HOOK_REPLY_ID, SubMsgResult::Err(error)) => Ok(
+            Response::new()
+                .add_attribute("action", "hook_failed")
+                .add_attribute("error", error)
+        ),
+        _ => Ok(Response::new()),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -43,30 +121,135 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // trading is blocked while entries are still stored in the previous layout
+    if MIGRATION_PROGRESS.exists(deps.storage) && !matches!(msg, ExecuteMsg::ContinueMigration { .. }) {
+        return Err(ContractError::MigrationInProgress {  });
+    }
+
+    if sudo::is_allowed_while_blocked(&msg) {
+        sudo::ensure_not_paused(&deps)?;
+    } else {
+        sudo::ensure_not_paused_or_blocked(&deps, info.sender.as_str())?;
+    }
+
     match msg {
-        ExecuteMsg::List { price, nft_contract_address, token_id } => listing::list(deps, info, env, price, nft_contract_address, token_id),
+        ExecuteMsg::List { price, nft_contract_address, token_id, reserved_for, reservation_expires_at, on_reservation_expiry, fill_collection_bid, escrowless, denom, cw20_contract_address } => listing::list(deps, info, env, price, nft_contract_address, token_id, reserved_for, reservation_expires_at, on_reservation_expiry, fill_collection_bid, escrowless, denom, cw20_contract_address),
         ExecuteMsg::Delist { new_price, nft_contract_address, token_id } => listing::delist(deps, info, nft_contract_address, token_id, new_price),
-        ExecuteMsg::BuyListing { nft_contract_address, token_id } => listing::buy_listing(deps, info, nft_contract_address, token_id),
+        ExecuteMsg::BuyListing { nft_contract_address, token_id, referrer } => listing::buy_listing(deps, info, env, nft_contract_address, token_id, referrer),
         ExecuteMsg::CancelListing { nft_contract_address, token_id } => listing::cancel_listing(deps, info, nft_contract_address, token_id),
-        ExecuteMsg::Bid { price, nft_contract_address, token_id } => bidding::bid(deps, info, price, nft_contract_address, token_id),
-        ExecuteMsg::UpdateBid { new_price, nft_contract_address, token_id } => bidding::update_bid(deps, info, nft_contract_address, token_id, new_price),
+        ExecuteMsg::ReturnExpiredListing { nft_contract_address, token_id } => listing::return_expired_listing(deps, env, nft_contract_address, token_id),
+        ExecuteMsg::InvalidateStaleListing { nft_contract_address, token_id } => listing::invalidate_stale_listing(deps, env, nft_contract_address, token_id),
+        ExecuteMsg::Bid { price, nft_contract_address, token_id, use_balance, denom } => bidding::bid(deps, info, env, price, nft_contract_address, token_id, use_balance, denom),
+        ExecuteMsg::UpdateBid { new_price, nft_contract_address, token_id } => bidding::update_bid(deps, info, env, nft_contract_address, token_id, new_price),
         ExecuteMsg::CancelBid { nft_contract_address, token_id } => bidding::cancel_bid(deps, info, nft_contract_address, token_id),
-        ExecuteMsg::SellToBid { nft_contract_address, token_id, bidder } => bidding::sell_to_bid(deps, info, nft_contract_address, token_id, bidder),
-        ExecuteMsg::CollectionBid { prices, nft_contract_address } => collection_bidding::collection_bid(deps, info, prices, nft_contract_address),
+        ExecuteMsg::SellToBid { nft_contract_address, token_id, bidder, referrer } => bidding::sell_to_bid(deps, info, env, nft_contract_address, token_id, bidder, referrer),
+        ExecuteMsg::CollectionBid { prices, nft_contract_address, use_balance, denom } => collection_bidding::collection_bid(deps, info, env, prices, nft_contract_address, use_balance, denom),
         ExecuteMsg::CancelAllCollectionBids { nft_contract_address } => collection_bidding::cancel_all_collection_bids(deps, info, nft_contract_address),
         ExecuteMsg::CancelCollectionBid { nft_contract_address, price } => collection_bidding::cancel_collection_bid(deps, info, nft_contract_address, price),
-        ExecuteMsg::SellToCollectionBid { nft_contract_address, token_id, bidder, price } => collection_bidding::sell_to_collection_bid(deps, info, nft_contract_address, token_id, bidder, price),
+        ExecuteMsg::SellToCollectionBid { nft_contract_address, token_id, bidder, price, referrer } => collection_bidding::sell_to_collection_bid(deps, info, env, nft_contract_address, token_id, bidder, price, referrer),
+        ExecuteMsg::TraitBid { price, nft_contract_address, traits } => trait_bidding::trait_bid(deps, info, env, price, nft_contract_address, traits),
+        ExecuteMsg::CancelTraitBid { nft_contract_address, trait_bid_id } => trait_bidding::cancel_trait_bid(deps, info, nft_contract_address, trait_bid_id),
+        ExecuteMsg::SellToTraitBid { nft_contract_address, token_id, trait_bid_id, referrer } => trait_bidding::sell_to_trait_bid(deps, info, env, nft_contract_address, token_id, trait_bid_id, referrer),
+        ExecuteMsg::TokenSetBid { price, nft_contract_address, merkle_root } => token_set_bidding::token_set_bid(deps, info, env, price, nft_contract_address, merkle_root),
+        ExecuteMsg::CancelTokenSetBid { nft_contract_address, token_set_bid_id } => token_set_bidding::cancel_token_set_bid(deps, info, nft_contract_address, token_set_bid_id),
+        ExecuteMsg::SellToTokenSetBid { nft_contract_address, token_id, token_set_bid_id, proof, referrer } => token_set_bidding::sell_to_token_set_bid(deps, info, env, nft_contract_address, token_id, token_set_bid_id, proof, referrer),
+        ExecuteMsg::ListBundle { items, price, royalty_rule } => bundle::list_bundle(deps, info, env, items, price, royalty_rule),
+        ExecuteMsg::BuyBundle { bundle_id, referrer } => bundle::buy_bundle(deps, info, env, bundle_id, referrer),
+        ExecuteMsg::CancelBundle { bundle_id } => bundle::cancel_bundle(deps, info, bundle_id),
+        ExecuteMsg::CreateSwap { offered_nfts, requested_nfts, counterparty } => swap::create_swap(deps, info, env, offered_nfts, requested_nfts, counterparty),
+        ExecuteMsg::AcceptSwap { swap_id } => swap::accept_swap(deps, info, env, swap_id),
+        ExecuteMsg::CancelSwap { swap_id } => swap::cancel_swap(deps, info, swap_id),
+        ExecuteMsg::Deposit {} => balance::deposit(deps, info),
+        ExecuteMsg::Withdraw { amount } => balance::withdraw(deps, info, amount),
+        ExecuteMsg::FillSignedOrder { order, signature, maker_pubkey, referrer } => signed_order::fill_signed_order(deps, info, env, order, signature, maker_pubkey, referrer),
+        ExecuteMsg::CancelSignedOrder { order } => signed_order::cancel_signed_order(deps, info, env, order),
+        ExecuteMsg::IncrementNonce {} => signed_order::increment_nonce(deps, info),
+        ExecuteMsg::SetCollectionRoyalties { nft_contract_address, recipients } => royalties::set_collection_royalties(deps, info, nft_contract_address, recipients),
+        ExecuteMsg::RemoveCollectionRoyalties { nft_contract_address } => royalties::remove_collection_royalties(deps, info, nft_contract_address),
+        ExecuteMsg::UpdateAdmin { admin } => admin::update_admin(deps, info, admin),
+        ExecuteMsg::UpdateReferralFeeShare { referral_fee_share } => admin::update_referral_fee_share(deps, info, referral_fee_share),
+        ExecuteMsg::UpdateFeeSchedule { fee_schedule } => fees::update_fee_schedule(deps, info, fee_schedule),
+        ExecuteMsg::SetCollectionFeeOverride { nft_contract_address, fee, starts_at, ends_at } => fees::set_collection_fee_override(deps, info, nft_contract_address, fee, starts_at, ends_at),
+        ExecuteMsg::RemoveCollectionFeeOverride { nft_contract_address } => fees::remove_collection_fee_override(deps, info, nft_contract_address),
+        ExecuteMsg::AddHook { contract_address } => hooks::add_hook(deps, info, contract_address),
+        ExecuteMsg::RemoveHook { contract_address } => hooks::remove_hook(deps, info, contract_address),
+        ExecuteMsg::UpdateHookGasLimit { hook_gas_limit } => hooks::update_hook_gas_limit(deps, info, hook_gas_limit),
+        ExecuteMsg::Receive(cw20_receive_msg) => cw20::receive(deps, info, env, cw20_receive_msg),
+        ExecuteMsg::AllowCw20 { contract_address } => cw20::allow_cw20(deps, info, contract_address),
+        ExecuteMsg::DisallowCw20 { contract_address } => cw20::disallow_cw20(deps, info, contract_address),
+        ExecuteMsg::AllowDenom { denom } => denoms::allow_denom(deps, info, denom),
+        ExecuteMsg::DisallowDenom { denom } => denoms::disallow_denom(deps, info, denom),
+        ExecuteMsg::ContinueMigration { limit } => migration::continue_migration(deps, limit),
+        ExecuteMsg::SetCollectionInfo { nft_contract_address, collection_info } => collections::set_collection_info(deps, info, nft_contract_address, collection_info),
+        ExecuteMsg::RemoveCollectionInfo { nft_contract_address } => collections::remove_collection_info(deps, info, nft_contract_address),
+        ExecuteMsg::UpdateCurationMode { curation_mode } => collections::update_curation_mode(deps, info, curation_mode),
+        ExecuteMsg::FreezeCollectionTrading { nft_contract_address, unfreeze_at } => collections::freeze_collection_trading(deps, info, env, nft_contract_address, unfreeze_at),
+        ExecuteMsg::UnfreezeCollectionTrading { nft_contract_address } => collections::unfreeze_collection_trading(deps, info, nft_contract_address),
+        ExecuteMsg::UpdateResaleCooldown { resale_cooldown_seconds } => admin::update_resale_cooldown(deps, info, resale_cooldown_seconds),
+        ExecuteMsg::UpdatePayoutMode { recipient_type, pull } => claims::update_payout_mode(deps, info, recipient_type, pull),
+        ExecuteMsg::Claim {} => claims::claim(deps, info),
+        ExecuteMsg::UpdateModerators { add, remove } => blocklist::update_moderators(deps, info, add, remove),
+        ExecuteMsg::UpdateAddressBlocklist { add, remove } => blocklist::update_address_blocklist(deps, info, add, remove),
+        ExecuteMsg::UpdateNftBlocklist { add, remove } => blocklist::update_nft_blocklist(deps, info, add, remove),
+        ExecuteMsg::ResolveBlockedNft { nft_contract_address, token_id, recipient } => blocklist::resolve_blocked_nft(deps, info, env, nft_contract_address, token_id, recipient),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(
+    deps: DepsMut,
+    _env: Env,
+    msg: SudoMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::UpdateFeeSchedule { fee_schedule } => fees::save_fee_schedule(deps.storage, fee_schedule),
+        SudoMsg::Pause {} => sudo::pause(deps, true),
+        SudoMsg::Unpause {} => sudo::pause(deps, false),
+        SudoMsg::UpdateBlocklist { add, remove } => sudo::update_blocklist(deps, add, remove),
+        SudoMsg::ForceReturnListing { nft_contract_address, token_id } => listing::force_return_listing(deps, nft_contract_address, token_id),
+        SudoMsg::ForceReturnBid { nft_contract_address, token_id, bidder } => bidding::force_return_bid(deps, nft_contract_address, token_id, bidder),
+        SudoMsg::ForceReturnCollectionBids { nft_contract_address, bidder } => collection_bidding::force_return_collection_bids(deps, nft_contract_address, bidder),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetNftListing { nft_contract_address, token_id } => to_json_binary(&query::get_nft_listing(deps, nft_contract_address, token_id)?),
+        QueryMsg::GetPaginatedReservedListings { buyer, start_after, limit } => to_json_binary(&query::query_paginated_reserved_listings(deps, buyer, start_after, limit)?),
         QueryMsg::GetNftBid { nft_contract_address, token_id, bidder } => to_json_binary(&query::get_nft_bid(deps, nft_contract_address, token_id, bidder)?),
         QueryMsg::GetNftCollectionBid { nft_contract_address, bidder } => to_json_binary(&query::get_nft_collection_bid(deps, nft_contract_address, bidder)?),
-        QueryMsg::GetPaginatedListings { nft_contract_address, start_after, limit } => to_json_binary(&query::query_paginated_listings(deps, nft_contract_address, start_after.as_deref(), limit)?),
-        QueryMsg::GetPaginatedBids { nft_contract_address, token_id, start_after, limit } => to_json_binary(&query::query_paginated_bids(deps, nft_contract_address, token_id, start_after.as_deref(), limit)?),
-        QueryMsg::GetPaginatedCollectionBids { nft_contract_address, start_after, limit } => to_json_binary(&query::query_paginated_collection_bids(deps, nft_contract_address, start_after.as_deref(), limit)?),
+        QueryMsg::GetPaginatedListings { nft_contract_address, start_after, limit, denom } => to_json_binary(&query::query_paginated_listings(deps, nft_contract_address, start_after.as_deref(), limit, denom)?),
+        QueryMsg::GetPaginatedBids { nft_contract_address, token_id, start_after, limit, denom } => to_json_binary(&query::query_paginated_bids(deps, nft_contract_address, token_id, start_after.as_deref(), limit, denom)?),
+        QueryMsg::GetPaginatedCollectionBids { nft_contract_address, start_after, limit, denom } => to_json_binary(&query::query_paginated_collection_bids(deps, nft_contract_address, start_after.as_deref(), limit, denom)?),
+        QueryMsg::GetNftTraitBid { nft_contract_address, trait_bid_id } => to_json_binary(&query::get_nft_trait_bid(deps, nft_contract_address, trait_bid_id)?),
+        QueryMsg::GetPaginatedTraitBids { nft_contract_address, start_after, limit } => to_json_binary(&query::query_paginated_trait_bids(deps, nft_contract_address, start_after, limit)?),
+        QueryMsg::GetNftTokenSetBid { nft_contract_address, token_set_bid_id } => to_json_binary(&query::get_nft_token_set_bid(deps, nft_contract_address, token_set_bid_id)?),
+        QueryMsg::GetPaginatedTokenSetBids { nft_contract_address, start_after, limit } => to_json_binary(&query::query_paginated_token_set_bids(deps, nft_contract_address, start_after, limit)?),
+        QueryMsg::GetNftBundle { bundle_id } => to_json_binary(&query::get_nft_bundle(deps, bundle_id)?),
+        QueryMsg::GetPaginatedBundles { start_after, limit } => to_json_binary(&query::query_paginated_bundles(deps, start_after, limit)?),
+        QueryMsg::GetNftSwap { swap_id } => to_json_binary(&query::get_nft_swap(deps, swap_id)?),
+        QueryMsg::GetPaginatedSwapsByMaker { maker, start_after, limit } => to_json_binary(&query::query_paginated_swaps_by_maker(deps, maker, start_after, limit)?),
+        QueryMsg::GetPaginatedSwapsByTaker { taker, start_after, limit } => to_json_binary(&query::query_paginated_swaps_by_taker(deps, taker, start_after, limit)?),
+        QueryMsg::GetBalance { account } => to_json_binary(&query::get_balance(deps, account)?),
+        QueryMsg::GetMakerNonce { maker } => to_json_binary(&query::get_maker_nonce(deps, maker)?),
+        QueryMsg::GetSignedOrderStatus { order } => to_json_binary(&query::get_signed_order_status(deps, env, order)?),
+        QueryMsg::GetConfig {} => to_json_binary(&query::get_config(deps)?),
+        QueryMsg::GetHooks {} => to_json_binary(&query::get_hooks(deps)?),
+        QueryMsg::GetEffectiveFee { account } => to_json_binary(&query::get_effective_fee(deps, env, account)?),
+        QueryMsg::GetPaginatedCollectionFeeOverrides { start_after, limit } => to_json_binary(&query::query_paginated_collection_fee_overrides(deps, start_after.as_deref(), limit)?),
+        QueryMsg::GetCollectionRoyalties { nft_contract_address } => to_json_binary(&query::get_collection_royalties(deps, nft_contract_address)?),
+        QueryMsg::GetReferralEarnings { referrer } => to_json_binary(&query::get_referral_earnings(deps, referrer)?),
+        QueryMsg::GetCw20Allowlist {} => to_json_binary(&query::get_cw20_allowlist(deps)?),
+        QueryMsg::GetDenomAllowlist {} => to_json_binary(&query::get_denom_allowlist(deps)?),
+        QueryMsg::GetMigrationProgress {} => to_json_binary(&query::get_migration_progress(deps)?),
+        QueryMsg::GetCollectionInfo { nft_contract_address } => to_json_binary(&query::get_collection_info(deps, nft_contract_address)?),
+        QueryMsg::GetPaginatedVerifiedCollections { start_after, limit } => to_json_binary(&query::query_paginated_verified_collections(deps, start_after.as_deref(), limit)?),
+        QueryMsg::GetCollectionFreeze { nft_contract_address } => to_json_binary(&query::get_collection_freeze(deps, env, nft_contract_address)?),
+        QueryMsg::GetClaimableBalances { recipient } => to_json_binary(&query::get_claimable_balances(deps, recipient)?),
+        QueryMsg::GetModerators {} => to_json_binary(&query::get_moderators(deps)?),
+        QueryMsg::GetPaginatedBlockedAddresses { start_after, limit } => to_json_binary(&query::query_paginated_blocked_addresses(deps, start_after.as_deref(), limit)?),
+        QueryMsg::GetPaginatedModeratedBlockedAddresses { start_after, limit } => to_json_binary(&query::query_paginated_moderated_blocked_addresses(deps, start_after.as_deref(), limit)?),
+        QueryMsg::GetPaginatedBlockedNfts { start_after, limit } => to_json_binary(&query::query_paginated_blocked_nfts(deps, start_after, limit)?),
     }
 }
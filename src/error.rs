@@ -65,4 +65,239 @@ pub enum ContractError {
 
     #[error("Bid already exists")]
     BidAlreadyExists {},
+
+    #[error("Error creating new trait bid")]
+    ErrorCreatingNewTraitBid {},
+
+    #[error("Invalid traits")]
+    InvalidTraits {},
+
+    #[error("NFT trait bid not found")]
+    NftTraitBidNotFound {},
+
+    #[error("NFT does not match trait bid")]
+    NftTraitsMismatch {},
+
+    #[error("NFT not approved for the marketplace")]
+    NftNotApproved {},
+
+    #[error("NFT not owned by seller")]
+    NftNotOwnedBySeller {},
+    #[error("Error creating new token set bid")]
+    ErrorCreatingNewTokenSetBid {},
+
+    #[error("Invalid merkle root")]
+    InvalidMerkleRoot {},
+
+    #[error("NFT token set bid not found")]
+    NftTokenSetBidNotFound {},
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("Invalid bundle items")]
+    InvalidBundleItems {},
+
+    #[error("Invalid bundle royalty rule")]
+    InvalidBundleRoyaltyRule {},
+
+    #[error("Error creating new bundle")]
+    ErrorCreatingNewBundle {},
+
+    #[error("NFT bundle not found")]
+    NftBundleNotFound {},
+
+    #[error("NFT is already in a bundle")]
+    NftAlreadyInBundle {},
+
+    #[error("Duplicate NFT items")]
+    DuplicateNftItems {},
+
+    #[error("Invalid swap items")]
+    InvalidSwapItems {},
+
+    #[error("Invalid counterparty")]
+    InvalidCounterparty {},
+
+    #[error("Error creating new swap")]
+    ErrorCreatingNewSwap {},
+
+    #[error("NFT swap not found")]
+    NftSwapNotFound {},
+
+    #[error("NFT is already in a swap")]
+    NftAlreadyInSwap {},
+
+    #[error("Invalid reserved buyer")]
+    InvalidReservedBuyer {},
+
+    #[error("Invalid reservation expiry")]
+    InvalidReservationExpiry {},
+
+    #[error("Listing is reserved for another buyer")]
+    ListingReservedForAnotherBuyer {},
+
+    #[error("Listing reservation expired")]
+    ListingReservationExpired {},
+
+    #[error("Listing reservation not expired")]
+    ListingReservationNotExpired {},
+
+    #[error("Reserved listing can't fill collection bid")]
+    ReservedListingCantFillCollectionBid {},
+
+    #[error("Insufficient balance")]
+    InsufficientBalance {},
+
+    #[error("Invalid amount")]
+    InvalidAmount {},
+
+    #[error("Bid funding does not match existing collection bid")]
+    BidFundingMismatch {},
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Signed order expired")]
+    SignedOrderExpired {},
+
+    #[error("Signed order nonce is no longer valid")]
+    InvalidNonce {},
+
+    #[error("Signed order already cancelled or filled")]
+    SignedOrderCancelledOrFilled {},
+
+    #[error("NFT not owned by maker")]
+    NftNotOwnedByMaker {},
+
+    #[error("NFT not owned by lister")]
+    NftNotOwnedByLister {},
+
+    #[error("Listing is stale, lister no longer owns or approved the NFT")]
+    StaleListing {},
+
+    #[error("Listing is not stale")]
+    ListingNotStale {},
+
+    #[error("Invalid admin")]
+    InvalidAdmin {},
+
+    #[error("Config not set")]
+    ConfigNotSet {},
+
+    #[error("Invalid hook contract address")]
+    InvalidHookContractAddress {},
+
+    #[error("Hook already exists")]
+    HookAlreadyExists {},
+
+    #[error("Hook not found")]
+    HookNotFound {},
+
+    #[error("Invalid hook gas limit")]
+    InvalidHookGasLimit {},
+    #[error("Invalid referrer")]
+    InvalidReferrer {},
+
+    #[error("Invalid referral fee share")]
+    InvalidReferralFeeShare {},
+
+    #[error("Invalid fee schedule")]
+    InvalidFeeSchedule {},
+
+    #[error("Invalid collection fee override")]
+    InvalidCollectionFeeOverride {},
+
+    #[error("Collection fee override not found")]
+    CollectionFeeOverrideNotFound {},
+
+    #[error("Invalid royalty recipients")]
+    InvalidRoyaltyRecipients {},
+
+    #[error("Collection royalties not found")]
+    CollectionRoyaltiesNotFound {},
+
+    #[error("Invalid CW20 contract address")]
+    InvalidCw20ContractAddress {},
+
+    #[error("CW20 is not allowed as payment asset")]
+    Cw20NotAllowed {},
+
+    #[error("CW20 is already allowed as payment asset")]
+    Cw20AlreadyAllowed {},
+
+    #[error("Payment asset does not match the order")]
+    PaymentAssetMismatch {},
+
+    #[error("Only usei bids can be funded from the balance")]
+    BalanceFundingRequiresUsei {},
+
+    #[error("Invalid denom")]
+    InvalidDenom {},
+
+    #[error("Denom is not allowed as payment asset")]
+    DenomNotAllowed {},
+
+    #[error("Denom is already allowed as payment asset")]
+    DenomAlreadyAllowed {},
+
+    #[error("Only one of denom and cw20 contract address can be set")]
+    MultiplePaymentAssets {},
+
+    #[error("Invalid contract version")]
+    InvalidContractVersion {},
+
+    #[error("Cannot migrate from a different contract")]
+    MigrationContractMismatch {},
+
+    #[error("Cannot migrate to an older version")]
+    MigrationDowngrade {},
+
+    #[error("Migration in progress")]
+    MigrationInProgress {},
+
+    #[error("No migration in progress")]
+    NoMigrationInProgress {},
+
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("Address is blocked")]
+    AddressBlocked {},
+
+    #[error("Invalid blocklist address")]
+    InvalidBlocklistAddress {},
+
+    #[error("Collection is not verified")]
+    CollectionNotVerified {},
+
+    #[error("Collection not found")]
+    CollectionNotFound {},
+
+    #[error("Invalid moderator")]
+    InvalidModerator {},
+
+    #[error("Invalid recipient")]
+    InvalidRecipient {},
+
+    #[error("Nft is blocked")]
+    NftBlocked {},
+
+    #[error("Nft is not blocked")]
+    NftNotBlocked {},
+
+    #[error("Seller and buyer can't be the same address")]
+    SelfTrade {},
+
+    #[error("Nft can't be sold back to the address it was bought from before the resale cooldown ends")]
+    ResaleCooldownActive {},
+
+    #[error("Collection trading is frozen")]
+    CollectionFrozen {},
+
+    #[error("Invalid unfreeze time")]
+    InvalidUnfreezeTime {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
 }
@@ -1,3 +1,20 @@
 pub mod listing;
 pub mod bidding;
-pub mod collection_bidding;
\ No newline at end of file
+pub mod collection_bidding;
+pub mod trait_bidding;
+pub mod token_set_bidding;
+pub mod bundle;
+pub mod swap;
+pub mod balance;
+pub mod signed_order;
+pub mod admin;
+pub mod hooks;
+pub mod fees;
+pub mod royalties;
+pub mod cw20;
+pub mod denoms;
+pub mod sudo;
+pub mod collections;
+pub mod blocklist;
+pub mod wash_trading;
+pub mod claims;
\ No newline at end of file
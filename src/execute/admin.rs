@@ -0,0 +1,78 @@
+use cosmwasm_std::{Decimal, DepsMut, MessageInfo, Response, Storage};
+
+use crate::{state::{Config, CONFIG}, ContractError};
+
+
+pub fn update_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let admin = deps.api.addr_validate(admin.as_str())
+        .map_err(|_e| ContractError::InvalidAdmin {  })?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<Config, ContractError> {
+        config.admin = admin.clone();
+        Ok(config)
+    })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_admin")
+            .add_attribute("admin", admin)
+    )
+}
+
+pub fn update_referral_fee_share(
+    deps: DepsMut,
+    info: MessageInfo,
+    referral_fee_share: Decimal,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    // referrer can get at most the whole platform fee
+    if referral_fee_share > Decimal::one() {
+        return Err(ContractError::InvalidReferralFeeShare {  });
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> Result<Config, ContractError> {
+        config.referral_fee_share = referral_fee_share;
+        Ok(config)
+    })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_referral_fee_share")
+            .add_attribute("referral_fee_share", referral_fee_share.to_string())
+    )
+}
+
+pub fn update_resale_cooldown(
+    deps: DepsMut,
+    info: MessageInfo,
+    resale_cooldown_seconds: u64,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<Config, ContractError> {
+        config.resale_cooldown_seconds = resale_cooldown_seconds;
+        Ok(config)
+    })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_resale_cooldown")
+            .add_attribute("resale_cooldown_seconds", resale_cooldown_seconds.to_string())
+    )
+}
+
+pub fn ensure_admin(storage: &dyn Storage, info: &MessageInfo) -> Result<(), ContractError> {
+    let config = CONFIG.may_load(storage)?
+        .ok_or(ContractError::ConfigNotSet {  })?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {  });
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, Storage, Uint128};
+
+use crate::{state::{PaymentAsset, BALANCES}, utils::payment_msg, ContractError};
+
+
+pub fn deposit(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let sent_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == PaymentAsset::default().id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    if sent_amount.is_zero() {
+        return Err(ContractError::InsufficientFundsSent {  });
+    }
+
+    let balance = credit_balance(deps.storage, &info.sender, sent_amount)?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "deposit")
+            .add_attribute("account", info.sender)
+            .add_attribute("amount", sent_amount.to_string())
+            .add_attribute("balance", balance.to_string())
+    )
+}
+
+pub fn withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {  });
+    }
+
+    let balance = debit_balance(deps.storage, &info.sender, amount)?;
+
+    // transfer sei from deposit back to account
+    let transfer_sei_msg = payment_msg(&PaymentAsset::default(), info.sender.to_string(), amount)?;
+
+    Ok(
+        Response::new()
+            .add_message(transfer_sei_msg)
+            .add_attribute("action", "withdraw")
+            .add_attribute("account", info.sender)
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("balance", balance.to_string())
+    )
+}
+
+pub fn query_balance(storage: &dyn Storage, account: &Addr) -> Result<Uint128, ContractError> {
+    Ok(BALANCES.may_load(storage, account.as_str())?.unwrap_or_default())
+}
+
+// returns the new balance
+pub fn credit_balance(storage: &mut dyn Storage, account: &Addr, amount: Uint128) -> Result<Uint128, ContractError> {
+    let balance = query_balance(storage, account)?
+        .checked_add(amount)
+        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+    BALANCES.save(storage, account.as_str(), &balance)?;
+    Ok(balance)
+}
+
+// returns the new balance, fails when the account can't cover the amount
+pub fn debit_balance(storage: &mut dyn Storage, account: &Addr, amount: Uint128) -> Result<Uint128, ContractError> {
+    let balance = query_balance(storage, account)?
+        .checked_sub(amount)
+        .map_err(|_e| ContractError::InsufficientBalance {  })?;
+    if balance.is_zero() {
+        BALANCES.remove(storage, account.as_str());
+    } else {
+        BALANCES.save(storage, account.as_str(), &balance)?;
+    }
+    Ok(balance)
+}
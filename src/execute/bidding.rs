@@ -1,27 +1,61 @@
-use std::str::FromStr;
+use cosmwasm_std::{to_json_binary, Addr, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg};
 
-use cosmwasm_std::{coins, to_json_binary, BankMsg, Decimal, DepsMut, MessageInfo, Response, Uint128, WasmMsg};
-
-use crate::{state::{NftBid, NFT_BIDS, PLATFORM_FEE_RECEIVER}, utils::{add_transfer_sei_to_seller_msg_with_price_after_platform_fee, parse_decimal, query_check_royalties, query_royalty_info}, ContractError};
+use crate::{execute::{balance::{credit_balance, debit_balance, query_balance}, blocklist::{ensure_nft_not_blocked, is_address_blocked}, collections::ensure_collection_tradable, fees::FeeRole, hooks::add_trade_hook_msgs, listing::{is_listing_stale, remove_nft_listing}, wash_trading::ensure_not_wash_trade}, msg::{TradeHookAction, TradeHookMsg}, state::{BidFunding, NftBid, PaymentAsset, ReservationExpiry, NFT_BIDS, NFT_LISTINGS}, utils::{add_sale_payout_msgs, payment_msg, sale_buyer_total_amount, transfer_nft_msg, validate_native_payment_asset, validate_referrer, verify_seller_can_deliver_nft}, ContractError};
 
 
+#[allow(clippy::too_many_arguments)]
 pub fn bid(
     deps: DepsMut,
     info: MessageInfo,
-    price: String,
+    env: Env,
+    price: Uint128,
     nft_contract_address: String,
     token_id: String,
+    use_balance: Option<bool>,
+    denom: Option<String>,
+) -> Result<Response, ContractError> {
+    let payment_asset = validate_native_payment_asset(deps.storage, denom)?;
+
+    let sent_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == payment_asset.id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    place_bid(deps, env, info.sender, payment_asset, sent_amount, price, nft_contract_address, token_id, use_balance)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn place_bid(
+    mut deps: DepsMut,
+    env: Env,
+    bidder: Addr,
+    payment_asset: PaymentAsset,
+    sent_amount: Uint128,
+    price: Uint128,
+    nft_contract_address: String,
+    token_id: String,
+    use_balance: Option<bool>,
 ) -> Result<Response, ContractError> {
-    let price = Decimal::from_str(price.as_str())
-        .map_err(|_e| ContractError::InvalidPrice {  })?;
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+    ensure_nft_not_blocked(deps.storage, &nft_contract_address, token_id.as_str())?;
+
+    let funding = if use_balance.unwrap_or(false) { BidFunding::Balance } else { BidFunding::Escrow };
+
+    // deposit balance is kept in usei only
+    if funding == BidFunding::Balance && payment_asset != PaymentAsset::default() {
+        return Err(ContractError::BalanceFundingRequiresUsei {  });
+    }
 
     let nft_bid = NftBid {
-        bidder: info.sender,
+        bidder,
         price,
         nft_contract_address,
-        token_id
+        token_id,
+        funding,
+        payment_asset,
     };
 
     let key = (nft_bid.nft_contract_address.as_str(), nft_bid.token_id.as_str(), nft_bid.bidder.as_str());
@@ -38,36 +72,44 @@ pub fn bid(
     )
         .map_err(|_e| ContractError::ErrorCreatingNewBid {  })?;
 
-    let sent_amount = info
-        .funds
-        .iter()
-        .find(|coin| coin.denom == "usei".to_string())
-        .map_or(Uint128::zero(), |coin| coin.amount);
-
-    // Check if the sent amount is sufficient
-    if sent_amount < parse_decimal(price)? {
-        return Err(ContractError::InsufficientFundsSent {  });
+    match nft_bid.funding {
+        BidFunding::Escrow => {
+            // Check if the sent amount is sufficient
+            if sent_amount < price {
+                return Err(ContractError::InsufficientFundsSent {  });
+            }
+        },
+        // anything sent with a balance funded bid tops up the deposit
+        BidFunding::Balance => {
+            if !sent_amount.is_zero() {
+                credit_balance(deps.storage, &nft_bid.bidder, sent_amount)?;
+            }
+        }
     }
 
-    Ok(
-        Response::new()
-            .add_attribute("action", "bid")
-            .add_attribute("price", parse_decimal(nft_bid.price)?.to_string())
-            .add_attribute("bidder", nft_bid.bidder)
-            .add_attribute("nft_contract_address", nft_bid.nft_contract_address)
-            .add_attribute("token_id", nft_bid.token_id)
-    )
+    let response = Response::new()
+        .add_attribute("action", "bid")
+        .add_attribute("price", nft_bid.price.to_string())
+        .add_attribute("bidder", nft_bid.bidder.clone())
+        .add_attribute("nft_contract_address", nft_bid.nft_contract_address.clone())
+        .add_attribute("token_id", nft_bid.token_id.clone());
+
+    match_bid_with_listing(&mut deps, &env, &nft_bid, response)
 }
 
 pub fn sell_to_bid(
-    deps: DepsMut,
+    mut deps: DepsMut,
     info: MessageInfo,
+    env: Env,
     nft_contract_address: String,
     token_id: String,
     bidder: String,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
+    let referrer = validate_referrer(deps.api, referrer, &info.sender)?;
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
 
     let bidder = deps.api.addr_validate(bidder.as_str())
         .map_err(|_e| ContractError::InvalidBidder {  })?;
@@ -79,7 +121,13 @@ pub fn sell_to_bid(
 
     NFT_BIDS.remove(deps.storage, key);
 
-    // transfer nft from escrow to bidder
+    verify_seller_can_deliver_nft(&mut deps, &env, &nft_contract_address, token_id.clone(), &info.sender)?;
+
+    if nft_bid.funding == BidFunding::Balance {
+        debit_balance(deps.storage, &nft_bid.bidder, nft_bid.price)?;
+    }
+
+    // transfer nft from escrow or seller wallet to bidder
     let cw721_transfer_nft_msg = cw721::Cw721ExecuteMsg::TransferNft {
         recipient: nft_bid.bidder.to_string(),
         token_id: nft_bid.token_id.clone()
@@ -90,62 +138,40 @@ pub fn sell_to_bid(
         funds: vec![]
     };
 
-    // pay platform fee
-    let platform_fee = nft_bid.price * Decimal::percent(2);
-    let pay_platform_fee_msg = BankMsg::Send {
-        to_address: PLATFORM_FEE_RECEIVER.to_string(),
-        amount: coins(parse_decimal(platform_fee)?.u128(), "usei")
-    };
-
-    let price_after_platform_fee = nft_bid.price.checked_sub(platform_fee)
-        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
-
-    let mut response = Response::new()
+    let response = Response::new()
         .add_message(transfer_nft_msg)
-        .add_message(pay_platform_fee_msg)
         .add_attribute("action", "sell_to_bid")
-        .add_attribute("price", parse_decimal(nft_bid.price)?.to_string())
-        .add_attribute("bidder", nft_bid.bidder)
+        .add_attribute("price", nft_bid.price.to_string())
+        .add_attribute("bidder", nft_bid.bidder.clone())
         .add_attribute("seller", info.sender.to_string())
         .add_attribute("nft_contract_address", nft_bid.nft_contract_address.clone())
         .add_attribute("token_id", nft_bid.token_id.clone());
 
-    // pay royalties
-    let check_royalties_response_result = query_check_royalties(&deps, nft_bid.nft_contract_address.to_string());
-    match check_royalties_response_result {
-        Ok(check_royalties_response) => {
-            if check_royalties_response.royalty_payments {
-                let royalty_info_response = query_royalty_info(
-                    &deps,
-                    nft_bid.nft_contract_address.to_string(),
-                    nft_bid.token_id.clone(),
-                    parse_decimal(nft_bid.price)?,
-                )?;
-                if !royalty_info_response.address.is_empty() && royalty_info_response.royalty_amount > Uint128::zero() {
-                    let pay_royalties_msg = BankMsg::Send {
-                        to_address: royalty_info_response.address,
-                        amount: coins(royalty_info_response.royalty_amount.u128(), "usei")
-                    };
-                    let price_after_platform_fee_and_royalties = parse_decimal(price_after_platform_fee)?.checked_sub(royalty_info_response.royalty_amount)
-                        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
-                    let transfer_sei_msg = BankMsg::Send {
-                        to_address: info.sender.to_string(),
-                        amount: coins(price_after_platform_fee_and_royalties.u128(), "usei")
-                    };
-                    response = response.add_message(pay_royalties_msg).add_message(transfer_sei_msg)
-                } else {
-                    response = add_transfer_sei_to_seller_msg_with_price_after_platform_fee(info.sender.to_string(), parse_decimal(price_after_platform_fee)?.u128(), response);
-                }
-            } else {
-                response = add_transfer_sei_to_seller_msg_with_price_after_platform_fee(info.sender.to_string(), parse_decimal(price_after_platform_fee)?.u128(), response);
-            }
-        },
-        // if there is an error that means the nft contract does not support royalties
-        Err(_e) => {
-            response = add_transfer_sei_to_seller_msg_with_price_after_platform_fee(info.sender.to_string(), parse_decimal(price_after_platform_fee)?.u128(), response);
-        }
-    }
-    
+    let response = add_sale_payout_msgs(
+        &mut deps,
+        &env,
+        &nft_bid.nft_contract_address,
+        nft_bid.token_id.clone(),
+        nft_bid.price,
+        &info.sender,
+        FeeRole::Taker,
+        &nft_bid.bidder,
+        &nft_bid.payment_asset,
+        None,
+        referrer.as_ref(),
+        response,
+    )?;
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Sale,
+        collection: nft_bid.nft_contract_address.to_string(),
+        token_id: nft_bid.token_id,
+        price: nft_bid.price,
+        payment_asset: nft_bid.payment_asset.clone(),
+        buyer: Some(nft_bid.bidder.to_string()),
+        seller: info.sender.to_string(),
+    }, response)?;
+
     Ok(response)
 }
 
@@ -165,83 +191,233 @@ pub fn cancel_bid(
 
     NFT_BIDS.remove(deps.storage, key);
 
-    // transfer sei from escrow back to bidder
-    let transfer_sei_msg = BankMsg::Send {
-        to_address: nft_bid.bidder.to_string(),
-        amount: coins(parse_decimal(nft_bid.price)?.u128(), "usei")
-    };
-
-    let response = Response::new()
-        .add_message(transfer_sei_msg)
+    let mut response = Response::new()
         .add_attribute("action", "cancel_bid")
-        .add_attribute("price", parse_decimal(nft_bid.price)?.to_string())
-        .add_attribute("bidder", nft_bid.bidder)
+        .add_attribute("price", nft_bid.price.to_string())
+        .add_attribute("bidder", nft_bid.bidder.clone())
         .add_attribute("nft_contract_address", nft_bid.nft_contract_address.clone())
         .add_attribute("token_id", nft_bid.token_id.clone());
 
+    // transfer payment from escrow back to bidder, balance funded bids never locked anything
+    if nft_bid.funding == BidFunding::Escrow {
+        let transfer_payment_msg = payment_msg(&nft_bid.payment_asset, nft_bid.bidder.to_string(), nft_bid.price)?;
+        response = response.add_message(transfer_payment_msg);
+    }
+
     Ok(response)
 }
 
-pub fn update_bid(
+pub fn force_return_bid(
     deps: DepsMut,
+    nft_contract_address: String,
+    token_id: String,
+    bidder: String,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let bidder = deps.api.addr_validate(bidder.as_str())
+        .map_err(|_e| ContractError::InvalidBidder {  })?;
+
+    let key = (nft_contract_address.as_str(), token_id.as_str(), bidder.as_str());
+
+    let nft_bid = NFT_BIDS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftBidNotFound {  })?;
+
+    NFT_BIDS.remove(deps.storage, key);
+
+    let mut response = Response::new()
+        .add_attribute("action", "force_return_bid")
+        .add_attribute("price", nft_bid.price.to_string())
+        .add_attribute("bidder", nft_bid.bidder.clone())
+        .add_attribute("nft_contract_address", nft_bid.nft_contract_address.clone())
+        .add_attribute("token_id", nft_bid.token_id.clone());
+
+    // transfer payment from escrow back to bidder, balance funded bids never locked anything
+    if nft_bid.funding == BidFunding::Escrow {
+        let transfer_payment_msg = payment_msg(&nft_bid.payment_asset, nft_bid.bidder.to_string(), nft_bid.price)?;
+        response = response.add_message(transfer_payment_msg);
+    }
+
+    Ok(response)
+}
+
+pub fn update_bid(
+    mut deps: DepsMut,
     info: MessageInfo,
+    env: Env,
     nft_contract_address: String,
     token_id: String,
-    new_price: String,
+    new_price: Uint128,
 ) -> Result<Response, ContractError> {
-    let new_price = Decimal::from_str(new_price.as_str())
-        .map_err(|_e| ContractError::InvalidPrice {  })?;
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+    ensure_nft_not_blocked(deps.storage, &nft_contract_address, token_id.as_str())?;
 
-    let mut transfer_sei_msg = None;
+    let mut transfer_payment_msg = None;
+    let mut balance_top_up = Uint128::zero();
     let key = (nft_contract_address.as_str(), token_id.as_str(), info.sender.as_str());
 
     let nft_bid = NFT_BIDS.update(deps.storage, key, |nft_bid_option| -> Result<NftBid, ContractError> {
         match nft_bid_option {
             Some(nft_bid) => {
-                if new_price > nft_bid.price {
+                if new_price == nft_bid.price {
+                    return Err(ContractError::NewPriceCantBeSameAsOldPrice {  });
+                } else if nft_bid.funding == BidFunding::Balance {
+                    // nothing is locked for balance funded bids, anything sent tops up the deposit like when placing one
+                    balance_top_up = info
+                        .funds
+                        .iter()
+                        .find(|coin| coin.denom == nft_bid.payment_asset.id())
+                        .map_or(Uint128::zero(), |coin| coin.amount);
+                } else if new_price > nft_bid.price {
+                    // only native denoms can be added to the escrow here, cw20 bids have to be cancelled and placed again
+                    if !matches!(nft_bid.payment_asset, PaymentAsset::Native { .. }) {
+                        return Err(ContractError::PaymentAssetMismatch {  });
+                    }
                     let diff = new_price.checked_sub(nft_bid.price)
                         .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
                     let sent_amount = info
                         .funds
                         .iter()
-                        .find(|coin| coin.denom == "usei".to_string())
+                        .find(|coin| coin.denom == nft_bid.payment_asset.id())
                         .map_or(Uint128::zero(), |coin| coin.amount);
-                    if sent_amount < parse_decimal(diff)? {
+                    if sent_amount < diff {
                         return Err(ContractError::InsufficientFundsSent {  });
                     }
-                } else if new_price < nft_bid.price {
+                } else {
                     let diff = nft_bid.price.checked_sub(new_price)
                         .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
-                    transfer_sei_msg = Some(BankMsg::Send {
-                        to_address: info.sender.to_string(),
-                        amount: coins(parse_decimal(diff)?.u128(), "usei")
-                    });
-                } else {
-                    return Err(ContractError::NewPriceCantBeSameAsOldPrice {  });
+                    transfer_payment_msg = Some(payment_msg(&nft_bid.payment_asset, info.sender.to_string(), diff)?);
                 }
                 Ok(NftBid {
                     price: new_price,
                     bidder: nft_bid.bidder,
                     nft_contract_address: nft_bid.nft_contract_address,
-                    token_id: nft_bid.token_id
+                    token_id: nft_bid.token_id,
+                    funding: nft_bid.funding,
+                    payment_asset: nft_bid.payment_asset,
                 })
             },
             None => Err(ContractError::NftBidNotFound {  })
         }
     })?;
 
+    if !balance_top_up.is_zero() {
+        credit_balance(deps.storage, &nft_bid.bidder, balance_top_up)?;
+    }
+
     let mut response = Response::new()
         .add_attribute("action", "update_bid")
-        .add_attribute("new_price", parse_decimal(nft_bid.price)?.to_string())
-        .add_attribute("bidder", nft_bid.bidder)
+        .add_attribute("new_price", nft_bid.price.to_string())
+        .add_attribute("bidder", nft_bid.bidder.clone())
         .add_attribute("nft_contract_address", nft_bid.nft_contract_address.clone())
         .add_attribute("token_id", nft_bid.token_id.clone());
 
-    if let Some(transfer_sei_msg) = transfer_sei_msg {
-        response = response.add_message(transfer_sei_msg);
+    if let Some(transfer_payment_msg) = transfer_payment_msg {
+        response = response.add_message(transfer_payment_msg);
+    }
+
+    match_bid_with_listing(&mut deps, &env, &nft_bid, response)
+}
+
+// when the bid covers the listing price of the same nft with the taker fee and royalties, both orders are settled at the listing price
+// like a BuyListing of the bidder, the lister pays the maker fee and the bidder gets the rest of the bid back
+fn match_bid_with_listing(
+    deps: &mut DepsMut,
+    env: &Env,
+    nft_bid: &NftBid,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let listing_key = (nft_bid.nft_contract_address.as_str(), nft_bid.token_id.as_str());
+    let nft_listing = match NFT_LISTINGS.may_load(deps.storage, listing_key)? {
+        Some(nft_listing) => nft_listing,
+        None => return Ok(response),
+    };
+
+    if nft_listing.price > nft_bid.price || nft_listing.lister == nft_bid.bidder || nft_listing.payment_asset != nft_bid.payment_asset {
+        return Ok(response);
+    }
+
+    // a reserved listing can only be matched by a bid of a buyer who could buy it directly
+    if let Some(reservation) = &nft_listing.reservation {
+        if !reservation.is_expired(env.block.time) {
+            if nft_bid.bidder != reservation.buyer {
+                return Ok(response);
+            }
+        } else if reservation.on_expiry == ReservationExpiry::Return {
+            return Ok(response);
+        }
+    }
+
+    // a blocked lister or a sale back within the resale cooldown leaves the bid on the book
+    if is_address_blocked(deps.storage, nft_listing.lister.as_str())
+        || ensure_not_wash_trade(deps.storage, env, &nft_listing.nft_contract_address, nft_listing.token_id.as_str(), &nft_listing.lister, &nft_bid.bidder).is_err() {
+        return Ok(response);
+    }
+
+    // a stale escrowless listing is dropped and the bid stays on the book
+    if is_listing_stale(deps, env, &nft_listing)? {
+        remove_nft_listing(deps.storage, &nft_listing);
+        return Ok(response.add_attribute("invalidated_stale_listing", "true"));
     }
 
+    // a bid not covering the taker fee and royalties on top of the listing price stays on the book
+    let total_amount = sale_buyer_total_amount(deps, env, &nft_listing.nft_contract_address, nft_listing.token_id.clone(), nft_listing.price, &nft_bid.bidder, FeeRole::Maker)?;
+    if nft_bid.price < total_amount {
+        return Ok(response);
+    }
+
+    // an over-committed balance funded bid just stays on the book
+    let bid_funds = match nft_bid.funding {
+        BidFunding::Balance => {
+            if query_balance(deps.storage, &nft_bid.bidder)? < total_amount {
+                return Ok(response);
+            }
+            debit_balance(deps.storage, &nft_bid.bidder, total_amount)?;
+            total_amount
+        },
+        BidFunding::Escrow => nft_bid.price,
+    };
+
+    remove_nft_listing(deps.storage, &nft_listing);
+    NFT_BIDS.remove(deps.storage, (listing_key.0, listing_key.1, nft_bid.bidder.as_str()));
+
+    // transfer nft from escrow or lister wallet to bidder
+    let transfer_nft_msg = transfer_nft_msg(&nft_listing.nft_contract_address, nft_bid.bidder.to_string(), nft_listing.token_id.clone())?;
+
+    let response = response
+        .add_message(transfer_nft_msg)
+        .add_attribute("matched_listing_price", nft_listing.price.to_string())
+        .add_attribute("lister", nft_listing.lister.to_string());
+
+    // the bid funds pay like the funds sent with BuyListing, the part of an escrowed bid above the amount due is refunded
+
+    let response = add_sale_payout_msgs(
+        deps,
+        env,
+        &nft_listing.nft_contract_address,
+        nft_listing.token_id.clone(),
+        nft_listing.price,
+        &nft_listing.lister,
+        FeeRole::Maker,
+        &nft_bid.bidder,
+        &nft_listing.payment_asset,
+        Some(bid_funds),
+        None,
+        response,
+    )?;
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Sale,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price: nft_listing.price,
+        payment_asset: nft_listing.payment_asset.clone(),
+        buyer: Some(nft_bid.bidder.to_string()),
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
     Ok(response)
 }
\ No newline at end of file
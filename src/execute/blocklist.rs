@@ -0,0 +1,194 @@
+use cosmwasm_std::{Addr, DepsMut, Empty, Env, MessageInfo, Response, Storage};
+
+use crate::{execute::{admin::ensure_admin, bundle::remove_bundle, listing::remove_nft_listing, swap::remove_swap}, state::{PaymentAsset, BLOCKED_NFTS, BLOCKLIST, CONFIG, MODERATED_BLOCKLIST, MODERATORS, NFT_BUNDLED_ITEMS, NFT_BUNDLES, NFT_LISTINGS, NFT_SWAPS, NFT_SWAP_ITEMS}, utils::{payment_msg, query_nft_owner, transfer_nft_msg}, ContractError};
+
+
+pub fn update_moderators(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    for moderator in add.iter() {
+        let moderator = deps.api.addr_validate(moderator.as_str())
+            .map_err(|_e| ContractError::InvalidModerator {  })?;
+        MODERATORS.save(deps.storage, moderator.as_str(), &Empty {})?;
+    }
+
+    for moderator in remove.iter() {
+        let moderator = deps.api.addr_validate(moderator.as_str())
+            .map_err(|_e| ContractError::InvalidModerator {  })?;
+        MODERATORS.remove(deps.storage, moderator.as_str());
+    }
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_moderators")
+            .add_attribute("added", add.join(","))
+            .add_attribute("removed", remove.join(","))
+    )
+}
+
+pub fn update_address_blocklist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure_admin_or_moderator(deps.storage, &info)?;
+
+    for address in add.iter() {
+        let address = deps.api.addr_validate(address.as_str())
+            .map_err(|_e| ContractError::InvalidBlocklistAddress {  })?;
+        MODERATED_BLOCKLIST.save(deps.storage, address.as_str(), &Empty {})?;
+    }
+
+    for address in remove.iter() {
+        let address = deps.api.addr_validate(address.as_str())
+            .map_err(|_e| ContractError::InvalidBlocklistAddress {  })?;
+        MODERATED_BLOCKLIST.remove(deps.storage, address.as_str());
+    }
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_address_blocklist")
+            .add_attribute("added", add.join(","))
+            .add_attribute("removed", remove.join(","))
+    )
+}
+
+pub fn update_nft_blocklist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<(String, String)>,
+    remove: Vec<(String, String)>,
+) -> Result<Response, ContractError> {
+    ensure_admin_or_moderator(deps.storage, &info)?;
+
+    for (nft_contract_address, token_id) in add.iter() {
+        let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+            .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+        BLOCKED_NFTS.save(deps.storage, (nft_contract_address.as_str(), token_id.as_str()), &Empty {})?;
+    }
+
+    for (nft_contract_address, token_id) in remove.iter() {
+        let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+            .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+        BLOCKED_NFTS.remove(deps.storage, (nft_contract_address.as_str(), token_id.as_str()));
+    }
+
+    let nfts_separated_by_comma = |nfts: &Vec<(String, String)>| nfts
+        .iter()
+        .map(|(nft_contract_address, token_id)| format!("{}:{}", nft_contract_address, token_id))
+        .collect::<Vec<String>>().join(",");
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_nft_blocklist")
+            .add_attribute("added", nfts_separated_by_comma(&add))
+            .add_attribute("removed", nfts_separated_by_comma(&remove))
+    )
+}
+
+// admin only, closes every order holding the blocked nft, unblocks it and sends it from escrow to the recipient
+pub fn resolve_blocked_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    nft_contract_address: String,
+    token_id: String,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    let recipient = deps.api.addr_validate(recipient.as_str())
+        .map_err(|_e| ContractError::InvalidRecipient {  })?;
+
+    let key = (nft_contract_address.as_str(), token_id.as_str());
+
+    if !BLOCKED_NFTS.has(deps.storage, key) {
+        return Err(ContractError::NftNotBlocked {  });
+    }
+    BLOCKED_NFTS.remove(deps.storage, key);
+
+    let mut response = Response::new()
+        .add_attribute("action", "resolve_blocked_nft")
+        .add_attribute("nft_contract_address", nft_contract_address.clone())
+        .add_attribute("token_id", token_id.clone())
+        .add_attribute("recipient", recipient.clone());
+
+    if let Some(nft_listing) = NFT_LISTINGS.may_load(deps.storage, key)? {
+        remove_nft_listing(deps.storage, &nft_listing);
+        response = response.add_attribute("lister", nft_listing.lister);
+    }
+
+    // the rest of the bundle goes back to the lister
+    if let Some(bundle_id) = NFT_BUNDLED_ITEMS.may_load(deps.storage, key)? {
+        let nft_bundle = NFT_BUNDLES.load(deps.storage, bundle_id)?;
+        remove_bundle(deps.storage, &nft_bundle);
+        for item in nft_bundle.items.iter().filter(|item| item.nft_contract_address != nft_contract_address || item.token_id != token_id) {
+            let transfer_nft_msg = transfer_nft_msg(&item.nft_contract_address, nft_bundle.lister.to_string(), item.token_id.clone())?;
+            response = response.add_message(transfer_nft_msg);
+        }
+        response = response.add_attribute("bundle_id", bundle_id.to_string());
+    }
+
+    // the rest of the swap offer goes back to the maker
+    if let Some(swap_id) = NFT_SWAP_ITEMS.may_load(deps.storage, key)? {
+        let nft_swap = NFT_SWAPS.load(deps.storage, swap_id)?;
+        remove_swap(deps.storage, &nft_swap);
+        for item in nft_swap.offered_nfts.iter().filter(|item| item.nft_contract_address != nft_contract_address || item.token_id != token_id) {
+            let transfer_nft_msg = transfer_nft_msg(&item.nft_contract_address, nft_swap.maker.to_string(), item.token_id.clone())?;
+            response = response.add_message(transfer_nft_msg);
+        }
+        if !nft_swap.offered_amount.is_zero() {
+            let transfer_sei_msg = payment_msg(&PaymentAsset::default(), nft_swap.maker.to_string(), nft_swap.offered_amount)?;
+            response = response.add_message(transfer_sei_msg);
+        }
+        response = response.add_attribute("swap_id", swap_id.to_string());
+    }
+
+    // escrowless nfts never left the owner wallet
+    if query_nft_owner(&deps, &nft_contract_address, token_id.clone())? == env.contract.address {
+        let transfer_nft_msg = transfer_nft_msg(&nft_contract_address, recipient.to_string(), token_id)?;
+        response = response.add_message(transfer_nft_msg);
+    }
+
+    Ok(response)
+}
+
+pub fn ensure_admin_or_moderator(storage: &dyn Storage, info: &MessageInfo) -> Result<(), ContractError> {
+    if MODERATORS.has(storage, info.sender.as_str()) {
+        return Ok(());
+    }
+    let config = CONFIG.may_load(storage)?
+        .ok_or(ContractError::ConfigNotSet {  })?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {  });
+    }
+    Ok(())
+}
+
+// blocked by governance through sudo or by the admin or a moderator
+pub fn is_address_blocked(storage: &dyn Storage, address: &str) -> bool {
+    BLOCKLIST.has(storage, address) || MODERATED_BLOCKLIST.has(storage, address)
+}
+
+pub fn ensure_address_not_blocked(storage: &dyn Storage, address: &Addr) -> Result<(), ContractError> {
+    if is_address_blocked(storage, address.as_str()) {
+        return Err(ContractError::AddressBlocked {  });
+    }
+    Ok(())
+}
+
+// blocked nfts can't be listed, bid on, sold or leave escrow until resolved
+pub fn ensure_nft_not_blocked(storage: &dyn Storage, nft_contract_address: &Addr, token_id: &str) -> Result<(), ContractError> {
+    if BLOCKED_NFTS.has(storage, (nft_contract_address.as_str(), token_id)) {
+        return Err(ContractError::NftBlocked {  });
+    }
+    Ok(())
+}
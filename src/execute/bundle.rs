@@ -0,0 +1,237 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Storage, Uint128};
+
+use crate::{execute::{blocklist::ensure_nft_not_blocked, collections::ensure_collection_tradable, fees::FeeRole, hooks::add_trade_hook_msgs}, msg::{TradeHookAction, TradeHookMsg}, state::{BundleRoyaltyRule, NftBundle, PaymentAsset, NFT_BUNDLED_ITEMS, NFT_BUNDLES, NFT_BUNDLES_COUNT, NFT_LISTINGS, NFT_SWAP_ITEMS}, utils::{add_buyer_payment_msgs, add_sale_item_payout_msgs, has_duplicate_nft_items, nft_items_separated_by_comma, transfer_nft_msg, validate_nft_items, validate_referrer, verify_nft_in_escrow}, ContractError};
+
+
+pub fn list_bundle(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    items: Vec<(String, String)>,
+    price: Uint128,
+    royalty_rule: Option<BundleRoyaltyRule>,
+) -> Result<Response, ContractError> {
+    if items.is_empty() || items.len() >= 100 {
+        return Err(ContractError::InvalidBundleItems {  });
+    }
+
+    let royalty_rule = royalty_rule.unwrap_or(BundleRoyaltyRule::Equal);
+    if let BundleRoyaltyRule::Weighted { weights } = &royalty_rule {
+        if weights.len() != items.len() || weights.iter().all(|weight| *weight == 0) {
+            return Err(ContractError::InvalidBundleRoyaltyRule {  });
+        }
+    }
+
+    let items = validate_nft_items(deps.api, items)?;
+    if has_duplicate_nft_items(&items) {
+        return Err(ContractError::InvalidBundleItems {  });
+    }
+
+    let id = NFT_BUNDLES_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    NFT_BUNDLES_COUNT.save(deps.storage, &id)?;
+
+    for item in items.iter() {
+        ensure_collection_tradable(deps.storage, &env, &item.nft_contract_address)?;
+        ensure_nft_not_blocked(deps.storage, &item.nft_contract_address, item.token_id.as_str())?;
+
+        let key = (item.nft_contract_address.as_str(), item.token_id.as_str());
+
+        if NFT_LISTINGS.has(deps.storage, key) {
+            return Err(ContractError::ListingAlreadyExists {  });
+        }
+        if NFT_BUNDLED_ITEMS.has(deps.storage, key) {
+            return Err(ContractError::NftAlreadyInBundle {  });
+        }
+        if NFT_SWAP_ITEMS.has(deps.storage, key) {
+            return Err(ContractError::NftAlreadyInSwap {  });
+        }
+        NFT_BUNDLED_ITEMS.save(deps.storage, key, &id)?;
+
+        // verify that escrow has the NFT
+        verify_nft_in_escrow(&deps, &env, &item.nft_contract_address, item.token_id.clone())?;
+    }
+
+    let nft_bundle = NftBundle {
+        id,
+        lister: info.sender,
+        price,
+        items,
+        royalty_rule,
+    };
+
+    NFT_BUNDLES.save(deps.storage, id, &nft_bundle)
+        .map_err(|_e| ContractError::ErrorCreatingNewBundle {  })?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "list_bundle")
+        .add_attribute("bundle_id", id.to_string())
+        .add_attribute("price", nft_bundle.price.to_string())
+        .add_attribute("lister", nft_bundle.lister.clone())
+        .add_attribute("items", nft_items_separated_by_comma(&nft_bundle.items));
+
+    // notify hooks per item with its share of the bundle price
+    let item_prices = bundle_item_prices(nft_bundle.price, &nft_bundle);
+    for (item, item_price) in nft_bundle.items.iter().zip(item_prices) {
+        response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+            action: TradeHookAction::Listing,
+            collection: item.nft_contract_address.to_string(),
+            token_id: item.token_id.clone(),
+            price: item_price,
+            payment_asset: PaymentAsset::default(),
+            buyer: None,
+            seller: nft_bundle.lister.to_string(),
+        }, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn buy_bundle(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    bundle_id: u64,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    let referrer = validate_referrer(deps.api, referrer, &info.sender)?;
+
+    let nft_bundle = NFT_BUNDLES.load(deps.storage, bundle_id)
+        .map_err(|_e| ContractError::NftBundleNotFound {  })?;
+
+    remove_bundle(deps.storage, &nft_bundle);
+
+    let price = nft_bundle.price;
+    let payment_asset = PaymentAsset::default();
+
+    let mut response = Response::new()
+        .add_attribute("action", "buy_bundle")
+        .add_attribute("bundle_id", bundle_id.to_string())
+        .add_attribute("price", price.to_string())
+        .add_attribute("lister", nft_bundle.lister.clone())
+        .add_attribute("buyer", info.sender.to_string())
+        .add_attribute("items", nft_items_separated_by_comma(&nft_bundle.items));
+
+    // every item is sold for its share of the price like a listing bought on its own,
+    // the buyer pays the taker fee and royalties of all of them on top
+    let item_prices = bundle_item_prices(price, &nft_bundle);
+    let mut total_amount = Uint128::zero();
+    for (item, item_price) in nft_bundle.items.iter().zip(item_prices) {
+        ensure_collection_tradable(deps.storage, &env, &item.nft_contract_address)?;
+
+        let (item_response, item_total_amount) = add_sale_item_payout_msgs(
+            &mut deps,
+            &env,
+            &item.nft_contract_address,
+            item.token_id.clone(),
+            item_price,
+            &nft_bundle.lister,
+            FeeRole::Maker,
+            &info.sender,
+            &payment_asset,
+            true,
+            referrer.as_ref(),
+            response,
+        )?;
+        total_amount = total_amount.checked_add(item_total_amount)
+            .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+
+        response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+            action: TradeHookAction::Sale,
+            collection: item.nft_contract_address.to_string(),
+            token_id: item.token_id.clone(),
+            price: item_price,
+            payment_asset: payment_asset.clone(),
+            buyer: Some(info.sender.to_string()),
+            seller: nft_bundle.lister.to_string(),
+        }, item_response)?;
+
+        // transfer nft from escrow to buyer
+        let transfer_nft_msg = transfer_nft_msg(&item.nft_contract_address, info.sender.to_string(), item.token_id.clone())?;
+        response = response.add_message(transfer_nft_msg);
+    }
+
+    let sent_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == payment_asset.id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    add_buyer_payment_msgs(&payment_asset, &info.sender, sent_amount, total_amount, response)
+}
+
+pub fn cancel_bundle(
+    deps: DepsMut,
+    info: MessageInfo,
+    bundle_id: u64,
+) -> Result<Response, ContractError> {
+    let nft_bundle = NFT_BUNDLES.load(deps.storage, bundle_id)
+        .map_err(|_e| ContractError::NftBundleNotFound {  })?;
+
+    if info.sender != nft_bundle.lister {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    // blocked items stay in escrow until resolved
+    for item in nft_bundle.items.iter() {
+        ensure_nft_not_blocked(deps.storage, &item.nft_contract_address, item.token_id.as_str())?;
+    }
+
+    remove_bundle(deps.storage, &nft_bundle);
+
+    // transfer nfts from escrow back to lister
+    let transfer_nft_msgs = nft_bundle.items
+        .iter()
+        .map(|item| transfer_nft_msg(&item.nft_contract_address, nft_bundle.lister.to_string(), item.token_id.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut response = Response::new()
+        .add_messages(transfer_nft_msgs)
+        .add_attribute("action", "cancel_bundle")
+        .add_attribute("bundle_id", bundle_id.to_string())
+        .add_attribute("price", nft_bundle.price.to_string())
+        .add_attribute("lister", nft_bundle.lister.clone())
+        .add_attribute("items", nft_items_separated_by_comma(&nft_bundle.items));
+
+    // notify hooks per item with its share of the bundle price
+    let item_prices = bundle_item_prices(nft_bundle.price, &nft_bundle);
+    for (item, item_price) in nft_bundle.items.iter().zip(item_prices) {
+        response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+            action: TradeHookAction::Cancel,
+            collection: item.nft_contract_address.to_string(),
+            token_id: item.token_id.clone(),
+            price: item_price,
+            payment_asset: PaymentAsset::default(),
+            buyer: None,
+            seller: nft_bundle.lister.to_string(),
+        }, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn remove_bundle(storage: &mut dyn Storage, nft_bundle: &NftBundle) {
+    for item in nft_bundle.items.iter() {
+        NFT_BUNDLED_ITEMS.remove(storage, (item.nft_contract_address.as_str(), item.token_id.as_str()));
+    }
+    NFT_BUNDLES.remove(storage, nft_bundle.id);
+}
+
+// share of the bundle price attributed to every item, used as sale price of the item
+// the last item takes the rounding remainder so the shares add up to the price
+fn bundle_item_prices(price: Uint128, nft_bundle: &NftBundle) -> Vec<Uint128> {
+    let mut item_prices: Vec<Uint128> = match &nft_bundle.royalty_rule {
+        BundleRoyaltyRule::Equal => {
+            let items_count = nft_bundle.items.len() as u128;
+            nft_bundle.items.iter().map(|_| price.multiply_ratio(1u128, items_count)).collect()
+        },
+        BundleRoyaltyRule::Weighted { weights } => {
+            let total_weight: u128 = weights.iter().map(|weight| *weight as u128).sum();
+            weights.iter().map(|weight| price.multiply_ratio(*weight as u128, total_weight)).collect()
+        }
+    };
+    let remainder = price - item_prices.iter().copied().sum::<Uint128>();
+    if let Some(last_item_price) = item_prices.last_mut() {
+        *last_item_price += remainder;
+    }
+    item_prices
+}
@@ -0,0 +1,86 @@
+use cosmwasm_std::{DepsMut, MessageInfo, Order, Response, StdResult, Storage, Uint128};
+
+use crate::{execute::admin::ensure_admin, state::{ClaimableBalance, Config, PaymentAsset, PayoutRecipientType, CLAIMABLE_BALANCES, CONFIG}, utils::payment_msg, ContractError};
+
+
+// admin only, pull credits the recipient type's proceeds to claimable balances instead of sending them
+pub fn update_payout_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient_type: PayoutRecipientType,
+    pull: bool,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<Config, ContractError> {
+        config.pull_payouts.retain(|pull_recipient_type| *pull_recipient_type != recipient_type);
+        if pull {
+            config.pull_payouts.push(recipient_type.clone());
+        }
+        Ok(config)
+    })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_payout_mode")
+            .add_attribute("recipient_type", recipient_type.id())
+            .add_attribute("payout_mode", if pull { "pull" } else { "push" })
+    )
+}
+
+// sends every claimable balance of the sender
+pub fn claim(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let claimable_balances = CLAIMABLE_BALANCES
+        .prefix(info.sender.as_str())
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if claimable_balances.is_empty() {
+        return Err(ContractError::NothingToClaim {  });
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "claim")
+        .add_attribute("recipient", info.sender.to_string());
+
+    for (asset_id, claimable_balance) in claimable_balances {
+        CLAIMABLE_BALANCES.remove(deps.storage, (info.sender.as_str(), asset_id.as_str()));
+        let claim_msg = payment_msg(&claimable_balance.payment_asset, info.sender.to_string(), claimable_balance.amount)?;
+        response = response
+            .add_message(claim_msg)
+            .add_attribute(asset_id, claimable_balance.amount.to_string());
+    }
+
+    Ok(response)
+}
+
+// pays the recipient directly or credits its claimable balance, depending on the payout mode of its type
+// zero amounts are skipped, a bank send of nothing fails the whole transaction
+pub fn add_payout(
+    storage: &mut dyn Storage,
+    recipient_type: PayoutRecipientType,
+    payment_asset: &PaymentAsset,
+    recipient: String,
+    amount: Uint128,
+    response: Response,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Ok(response);
+    }
+
+    let is_pull = CONFIG.may_load(storage)?.is_some_and(|config| config.pull_payouts.contains(&recipient_type));
+    if !is_pull {
+        let payout_msg = payment_msg(payment_asset, recipient, amount)?;
+        return Ok(response.add_message(payout_msg));
+    }
+
+    CLAIMABLE_BALANCES.update(storage, (recipient.as_str(), payment_asset.id()), |claimable_balance| -> StdResult<ClaimableBalance> {
+        let amount = claimable_balance.map_or(Uint128::zero(), |claimable_balance| claimable_balance.amount).checked_add(amount)?;
+        Ok(ClaimableBalance { payment_asset: payment_asset.clone(), amount })
+    })?;
+
+    Ok(response.add_attribute(format!("{}_claimable", recipient_type.id()), recipient))
+}
@@ -1,47 +1,87 @@
-use std::str::FromStr;
+use cosmwasm_std::{to_json_binary, Addr, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128, WasmMsg};
+use cw_storage_plus::Bound;
 
-use cosmwasm_std::{coins, to_json_binary, BankMsg, Decimal, DepsMut, MessageInfo, Response, Uint128, WasmMsg};
-
-use crate::{state::{NftCollectionBid, NFT_COLLECTION_BIDS, PLATFORM_FEE_RECEIVER}, utils::{add_transfer_sei_to_seller_msg_with_price_after_platform_fee, parse_decimal, query_check_royalties, query_royalty_info}, ContractError};
+use crate::{execute::{balance::{credit_balance, debit_balance, query_balance}, collections::ensure_collection_tradable, fees::FeeRole, hooks::add_trade_hook_msgs}, msg::{TradeHookAction, TradeHookMsg}, state::{BidFunding, NftCollectionBid, PaymentAsset, NFT_COLLECTION_BIDS, NFT_COLLECTION_BID_PRICES}, utils::{add_sale_payout_msgs, payment_msg, validate_native_payment_asset, validate_referrer, verify_seller_can_deliver_nft}, ContractError};
 
 
 pub fn collection_bid(
     deps: DepsMut,
     info: MessageInfo,
-    prices: Vec<String>,
+    env: Env,
+    prices: Vec<Uint128>,
+    nft_contract_address: String,
+    use_balance: Option<bool>,
+    denom: Option<String>,
+) -> Result<Response, ContractError> {
+    let payment_asset = validate_native_payment_asset(deps.storage, denom)?;
+
+    let sent_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == payment_asset.id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    place_collection_bid(deps, env, info.sender, payment_asset, sent_amount, prices, nft_contract_address, use_balance)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn place_collection_bid(
+    deps: DepsMut,
+    env: Env,
+    bidder: Addr,
+    payment_asset: PaymentAsset,
+    sent_amount: Uint128,
+    prices: Vec<Uint128>,
     nft_contract_address: String,
+    use_balance: Option<bool>,
 ) -> Result<Response, ContractError> {
     if prices.len() >= 100 {
         return Err(ContractError::CollectionBidsExceed100 {  })
     }
 
-    let prices = prices.iter().map(|price| Decimal::from_str(price.as_str())
-        .map_err(|_e| ContractError::InvalidPrice {  })).collect::<Result<Vec<Decimal>, ContractError>>()?;
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
 
-    let sent_amount = info
-        .funds
-        .iter()
-        .find(|coin| coin.denom == "usei".to_string())
-        .map_or(Uint128::zero(), |coin| coin.amount);
+    let total_amount = prices.iter().try_fold(Uint128::zero(), |acc, x| acc.checked_add(*x))
+        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
 
-    let total_amount = prices.iter().fold(Decimal::zero(), |acc, x| acc + x);
-    let total_amount = parse_decimal(total_amount)?;
+    let funding = if use_balance.unwrap_or(false) { BidFunding::Balance } else { BidFunding::Escrow };
 
-    // Check if the sent amount is sufficient
-    if sent_amount < total_amount {
-        return Err(ContractError::InsufficientFundsSent {  });
+    // deposit balance is kept in usei only
+    if funding == BidFunding::Balance && payment_asset != PaymentAsset::default() {
+        return Err(ContractError::BalanceFundingRequiresUsei {  });
+    }
+
+    match funding {
+        BidFunding::Escrow => {
+            // Check if the sent amount is sufficient
+            if sent_amount < total_amount {
+                return Err(ContractError::InsufficientFundsSent {  });
+            }
+        },
+        // anything sent with balance funded bids tops up the deposit
+        BidFunding::Balance => {
+            if !sent_amount.is_zero() {
+                credit_balance(deps.storage, &bidder, sent_amount)?;
+            }
+        }
     }
 
     let nft_contract_address_clone = nft_contract_address.clone();
-    let sender_clone = info.sender.clone();
+    let sender_clone = bidder.clone();
     let key = (nft_contract_address_clone.as_str(), sender_clone.as_str());
     let nft_collection_bid = NFT_COLLECTION_BIDS.may_load(deps.storage, key)?;
     match nft_collection_bid {
         Some(mut nft_collection_bid) => {
+            if nft_collection_bid.funding != funding {
+                return Err(ContractError::BidFundingMismatch {  });
+            }
+            if nft_collection_bid.payment_asset != payment_asset {
+                return Err(ContractError::PaymentAssetMismatch {  });
+            }
             for price in prices.iter() {
-                nft_collection_bid.bids_prices.push(price.clone());
+                nft_collection_bid.bids_prices.push(*price);
             }
             if nft_collection_bid.bids_prices.len() >= 100 {
                 return Err(ContractError::CollectionBidsExceed100 {  });
@@ -52,12 +92,15 @@ pub fn collection_bid(
                 &nft_collection_bid
             )
                 .map_err(|_e| ContractError::ErrorCreatingNewCollectionBid {  })?;
+            index_collection_bid_prices(deps.storage, &nft_collection_bid, &prices)?;
         },
         None => {
             let nft_collection_bid = NftCollectionBid {
-                bidder: info.sender.clone(),
+                bidder: bidder.clone(),
                 nft_contract_address: nft_contract_address.clone(),
                 bids_prices: prices.clone(),
+                funding,
+                payment_asset,
             };
 
             NFT_COLLECTION_BIDS.save(
@@ -66,37 +109,41 @@ pub fn collection_bid(
                 &nft_collection_bid
             )
                 .map_err(|_e| ContractError::ErrorCreatingNewCollectionBid {  })?;
+            index_collection_bid_prices(deps.storage, &nft_collection_bid, &prices)?;
         }
     };
 
     let prices_separated_by_comma = prices
         .iter()
-        .map(|price| parse_decimal(*price).unwrap().to_string())
+        .map(|price| price.to_string())
         .collect::<Vec<String>>().join(",");
 
     Ok(
         Response::new()
             .add_attribute("action", "collection_bid")
             .add_attribute("total_amount", total_amount.to_string())
-            .add_attribute("bidder", info.sender)
+            .add_attribute("bidder", bidder)
             .add_attribute("nft_contract_address", nft_contract_address)
             .add_attribute("prices", prices_separated_by_comma)
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn sell_to_collection_bid(
-    deps: DepsMut,
+    mut deps: DepsMut,
     info: MessageInfo,
+    env: Env,
     nft_contract_address: String,
     token_id: String,
     bidder: String,
-    price: String,
+    price: Uint128,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
-    let price = Decimal::from_str(price.as_str())
-        .map_err(|_e| ContractError::InvalidPrice {  })?;
+    let referrer = validate_referrer(deps.api, referrer, &info.sender)?;
 
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
 
     let bidder = deps.api.addr_validate(bidder.as_str())
         .map_err(|_e| ContractError::InvalidCollectionBidder {  })?;
@@ -111,8 +158,9 @@ pub fn sell_to_collection_bid(
     } else {
         return Err(ContractError::NftCollectionBidPriceNotFound {  });
     }
+    unindex_collection_bid_prices(deps.storage, &nft_collection_bid, &[price])?;
 
-    if nft_collection_bid.bids_prices.len() == 0 {
+    if nft_collection_bid.bids_prices.is_empty() {
         NFT_COLLECTION_BIDS.remove(deps.storage, key);
     } else {
         NFT_COLLECTION_BIDS.save(
@@ -122,8 +170,14 @@ pub fn sell_to_collection_bid(
         )
             .map_err(|_e| ContractError::ErrorUpdatingCollectionBid {  })?;
     }
-    
-    // transfer nft from escrow to bidder
+
+    if nft_collection_bid.funding == BidFunding::Balance {
+        debit_balance(deps.storage, &nft_collection_bid.bidder, price)?;
+    }
+
+    verify_seller_can_deliver_nft(&mut deps, &env, &nft_contract_address, token_id.clone(), &info.sender)?;
+
+    // transfer nft from escrow or seller wallet to bidder
     let cw721_transfer_nft_msg = cw721::Cw721ExecuteMsg::TransferNft {
         recipient: nft_collection_bid.bidder.to_string(),
         token_id: token_id.clone()
@@ -134,62 +188,40 @@ pub fn sell_to_collection_bid(
         funds: vec![]
     };
 
-    // pay platform fee
-    let platform_fee = price * Decimal::percent(2);
-    let pay_platform_fee_msg = BankMsg::Send {
-        to_address: PLATFORM_FEE_RECEIVER.to_string(),
-        amount: coins(parse_decimal(platform_fee)?.u128(), "usei")
-    };
-
-    let price_after_platform_fee = price.checked_sub(platform_fee)
-        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
-
-    let mut response = Response::new()
+    let response = Response::new()
         .add_message(transfer_nft_msg)
-        .add_message(pay_platform_fee_msg)
         .add_attribute("action", "sell_to_collection_bid")
-        .add_attribute("price", parse_decimal(price)?.to_string())
-        .add_attribute("bidder", nft_collection_bid.bidder)
+        .add_attribute("price", price.to_string())
+        .add_attribute("bidder", nft_collection_bid.bidder.clone())
         .add_attribute("seller", info.sender.to_string())
         .add_attribute("nft_contract_address", nft_collection_bid.nft_contract_address.clone())
         .add_attribute("token_id", token_id.clone());
 
-    // pay royalties
-    let check_royalties_response_result = query_check_royalties(&deps, nft_collection_bid.nft_contract_address.to_string());
-    match check_royalties_response_result {
-        Ok(check_royalties_response) => {
-            if check_royalties_response.royalty_payments {
-                let royalty_info_response = query_royalty_info(
-                    &deps,
-                    nft_collection_bid.nft_contract_address.to_string(),
-                    token_id.clone(),
-                    parse_decimal(price)?,
-                )?;
-                if !royalty_info_response.address.is_empty() && royalty_info_response.royalty_amount > Uint128::zero() {
-                    let pay_royalties_msg = BankMsg::Send {
-                        to_address: royalty_info_response.address,
-                        amount: coins(royalty_info_response.royalty_amount.u128(), "usei")
-                    };
-                    let price_after_platform_fee_and_royalties = parse_decimal(price_after_platform_fee)?.checked_sub(royalty_info_response.royalty_amount)
-                        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
-                    let transfer_sei_msg = BankMsg::Send {
-                        to_address: info.sender.to_string(),
-                        amount: coins(price_after_platform_fee_and_royalties.u128(), "usei")
-                    };
-                    response = response.add_message(pay_royalties_msg).add_message(transfer_sei_msg)
-                } else {
-                    response = add_transfer_sei_to_seller_msg_with_price_after_platform_fee(info.sender.to_string(), parse_decimal(price_after_platform_fee)?.u128(), response);
-                }
-            } else {
-                response = add_transfer_sei_to_seller_msg_with_price_after_platform_fee(info.sender.to_string(), parse_decimal(price_after_platform_fee)?.u128(), response);
-            }
-        },
-        // if there is an error that means the nft contract does not support royalties
-        Err(_e) => {
-            response = add_transfer_sei_to_seller_msg_with_price_after_platform_fee(info.sender.to_string(), parse_decimal(price_after_platform_fee)?.u128(), response);
-        }
-    }
-    
+    let response = add_sale_payout_msgs(
+        &mut deps,
+        &env,
+        &nft_collection_bid.nft_contract_address,
+        token_id.clone(),
+        price,
+        &info.sender,
+        FeeRole::Taker,
+        &nft_collection_bid.bidder,
+        &nft_collection_bid.payment_asset,
+        None,
+        referrer.as_ref(),
+        response,
+    )?;
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Sale,
+        collection: nft_collection_bid.nft_contract_address.to_string(),
+        token_id,
+        price,
+        payment_asset: nft_collection_bid.payment_asset.clone(),
+        buyer: Some(nft_collection_bid.bidder.to_string()),
+        seller: info.sender.to_string(),
+    }, response)?;
+
     Ok(response)
 }
 
@@ -207,34 +239,69 @@ pub fn cancel_all_collection_bids(
         .map_err(|_e| ContractError::NftCollectionBidNotFound {  })?;
 
     NFT_COLLECTION_BIDS.remove(deps.storage, key);
+    unindex_collection_bid_prices(deps.storage, &nft_collection_bid, &nft_collection_bid.bids_prices)?;
 
-    let total_amount = nft_collection_bid.bids_prices.iter().fold(Decimal::zero(), |acc, x| acc + x);
-    let total_amount = parse_decimal(total_amount)?;
+    let total_amount = nft_collection_bid.bids_prices.iter().try_fold(Uint128::zero(), |acc, x| acc.checked_add(*x))
+        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
 
-    // transfer sei from escrow back to bidder
-    let transfer_sei_msg = BankMsg::Send {
-        to_address: nft_collection_bid.bidder.to_string(),
-        amount: coins(total_amount.u128(), "usei")
-    };
+    let mut response = Response::new()
+        .add_attribute("action", "cancel_all_collection_bids")
+        .add_attribute("bidder", nft_collection_bid.bidder.clone())
+        .add_attribute("nft_contract_address", nft_collection_bid.nft_contract_address.clone())
+        .add_attribute("total_amount", total_amount.to_string());
 
-    Ok(
-        Response::new()
-            .add_message(transfer_sei_msg)
-            .add_attribute("action", "cancel_all_collection_bids")
-            .add_attribute("bidder", nft_collection_bid.bidder)
-            .add_attribute("nft_contract_address", nft_collection_bid.nft_contract_address.clone())
-            .add_attribute("total_amount", total_amount.to_string())
-    )
+    // transfer payment from escrow back to bidder, balance funded bids never locked anything
+    if nft_collection_bid.funding == BidFunding::Escrow {
+        let transfer_payment_msg = payment_msg(&nft_collection_bid.payment_asset, nft_collection_bid.bidder.to_string(), total_amount)?;
+        response = response.add_message(transfer_payment_msg);
+    }
+
+    Ok(response)
+}
+
+pub fn force_return_collection_bids(
+    deps: DepsMut,
+    nft_contract_address: String,
+    bidder: String,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let bidder = deps.api.addr_validate(bidder.as_str())
+        .map_err(|_e| ContractError::InvalidCollectionBidder {  })?;
+
+    let key = (nft_contract_address.as_str(), bidder.as_str());
+
+    let nft_collection_bid = NFT_COLLECTION_BIDS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftCollectionBidNotFound {  })?;
+
+    NFT_COLLECTION_BIDS.remove(deps.storage, key);
+    unindex_collection_bid_prices(deps.storage, &nft_collection_bid, &nft_collection_bid.bids_prices)?;
+
+    let total_amount = nft_collection_bid.bids_prices.iter().try_fold(Uint128::zero(), |acc, x| acc.checked_add(*x))
+        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "force_return_collection_bids")
+        .add_attribute("bidder", nft_collection_bid.bidder.clone())
+        .add_attribute("nft_contract_address", nft_collection_bid.nft_contract_address.clone())
+        .add_attribute("total_amount", total_amount.to_string());
+
+    // transfer payment from escrow back to bidder, balance funded bids never locked anything
+    if nft_collection_bid.funding == BidFunding::Escrow {
+        let transfer_payment_msg = payment_msg(&nft_collection_bid.payment_asset, nft_collection_bid.bidder.to_string(), total_amount)?;
+        response = response.add_message(transfer_payment_msg);
+    }
+
+    Ok(response)
 }
 
 pub fn cancel_collection_bid(
     deps: DepsMut,
     info: MessageInfo,
     nft_contract_address: String,
-    price: String,
+    price: Uint128,
 ) -> Result<Response, ContractError> {
-    let price = Decimal::from_str(price.as_str())
-        .map_err(|_e| ContractError::InvalidPrice {  })?;
 
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
@@ -249,8 +316,9 @@ pub fn cancel_collection_bid(
     } else {
         return Err(ContractError::NftCollectionBidPriceNotFound {  });
     }
+    unindex_collection_bid_prices(deps.storage, &nft_collection_bid, &[price])?;
 
-    if nft_collection_bid.bids_prices.len() == 0 {
+    if nft_collection_bid.bids_prices.is_empty() {
         NFT_COLLECTION_BIDS.remove(deps.storage, key);
     } else {
         NFT_COLLECTION_BIDS.save(
@@ -261,20 +329,89 @@ pub fn cancel_collection_bid(
             .map_err(|_e| ContractError::ErrorUpdatingCollectionBid {  })?;
     }
 
-    let price = parse_decimal(price)?;
+    let mut response = Response::new()
+        .add_attribute("action", "cancel_collection_bid")
+        .add_attribute("bidder", nft_collection_bid.bidder.clone())
+        .add_attribute("nft_contract_address", nft_collection_bid.nft_contract_address.clone())
+        .add_attribute("price", price.to_string());
+
+    // transfer payment from escrow back to bidder, balance funded bids never locked anything
+    if nft_collection_bid.funding == BidFunding::Escrow {
+        let transfer_payment_msg = payment_msg(&nft_collection_bid.payment_asset, nft_collection_bid.bidder.to_string(), price)?;
+        response = response.add_message(transfer_payment_msg);
+    }
+
+    Ok(response)
+}
+
+// removes the highest collection bid price at or above min_price and returns (bidder, price)
+// bids of the seller and balance funded bids the bidder can't cover at the moment are skipped
+// only bids in the payment asset of the listing are ranged, from the highest price down to min_price
+pub fn take_best_collection_bid(
+    storage: &mut dyn Storage,
+    nft_contract_address: &Addr,
+    min_price: Uint128,
+    seller: &Addr,
+    payment_asset: &PaymentAsset,
+) -> Result<Option<(Addr, Uint128)>, ContractError> {
+    let mut best_bid: Option<(Uint128, NftCollectionBid)> = None;
+    let bid_prices = NFT_COLLECTION_BID_PRICES
+        .sub_prefix((nft_contract_address.as_str(), payment_asset.id()))
+        .keys(storage, Some(Bound::inclusive((min_price.u128(), ""))), None, Order::Descending);
+    for bid_price in bid_prices {
+        let (price, bidder) = bid_price?;
+        if bidder == seller.as_str() {
+            continue;
+        }
+        let price = Uint128::new(price);
+        let nft_collection_bid = NFT_COLLECTION_BIDS.load(storage, (nft_contract_address.as_str(), bidder.as_str()))?;
+        if nft_collection_bid.funding == BidFunding::Balance && query_balance(storage, &nft_collection_bid.bidder)? < price {
+            continue;
+        }
+        best_bid = Some((price, nft_collection_bid));
+        break;
+    }
 
-    // transfer sei from escrow back to bidder
-    let transfer_sei_msg = BankMsg::Send {
-        to_address: nft_collection_bid.bidder.to_string(),
-        amount: coins(price.u128(), "usei")
+    let Some((price, mut nft_collection_bid)) = best_bid else {
+        return Ok(None);
     };
 
-    Ok(
-        Response::new()
-            .add_message(transfer_sei_msg)
-            .add_attribute("action", "cancel_collection_bid")
-            .add_attribute("bidder", nft_collection_bid.bidder)
-            .add_attribute("nft_contract_address", nft_collection_bid.nft_contract_address.clone())
-            .add_attribute("price", price.to_string())
-    )
-}
\ No newline at end of file
+    let key = (nft_contract_address.as_str(), nft_collection_bid.bidder.as_str());
+    if let Some(index) = nft_collection_bid.bids_prices.iter().position(|&x| x == price) {
+        nft_collection_bid.bids_prices.remove(index);
+    }
+    unindex_collection_bid_prices(storage, &nft_collection_bid, &[price])?;
+    if nft_collection_bid.bids_prices.is_empty() {
+        NFT_COLLECTION_BIDS.remove(storage, key);
+    } else {
+        NFT_COLLECTION_BIDS.save(storage, key, &nft_collection_bid)
+            .map_err(|_e| ContractError::ErrorUpdatingCollectionBid {  })?;
+    }
+
+    if nft_collection_bid.funding == BidFunding::Balance {
+        debit_balance(storage, &nft_collection_bid.bidder, price)?;
+    }
+
+    Ok(Some((nft_collection_bid.bidder, price)))
+}
+
+// adds the prices to the price index of the collection bids
+pub fn index_collection_bid_prices(storage: &mut dyn Storage, nft_collection_bid: &NftCollectionBid, prices: &[Uint128]) -> StdResult<()> {
+    for price in prices {
+        let key = ((nft_collection_bid.nft_contract_address.as_str(), nft_collection_bid.payment_asset.id()), price.u128(), nft_collection_bid.bidder.as_str());
+        NFT_COLLECTION_BID_PRICES.update(storage, key, |count| -> StdResult<u32> { Ok(count.unwrap_or_default() + 1) })?;
+    }
+    Ok(())
+}
+
+// removes the prices from the price index of the collection bids
+pub fn unindex_collection_bid_prices(storage: &mut dyn Storage, nft_collection_bid: &NftCollectionBid, prices: &[Uint128]) -> StdResult<()> {
+    for price in prices {
+        let key = ((nft_collection_bid.nft_contract_address.as_str(), nft_collection_bid.payment_asset.id()), price.u128(), nft_collection_bid.bidder.as_str());
+        match NFT_COLLECTION_BID_PRICES.may_load(storage, key)? {
+            Some(count) if count > 1 => NFT_COLLECTION_BID_PRICES.save(storage, key, &(count - 1))?,
+            _ => NFT_COLLECTION_BID_PRICES.remove(storage, key),
+        }
+    }
+    Ok(())
+}
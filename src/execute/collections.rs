@@ -0,0 +1,138 @@
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, Storage, Timestamp};
+
+use crate::{execute::admin::ensure_admin, state::{CollectionFreeze, CollectionInfo, Config, COLLECTIONS, COLLECTION_FREEZES, CONFIG}, utils::is_collection_creator, ContractError};
+
+
+pub fn set_collection_info(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+    collection_info: CollectionInfo,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    COLLECTIONS.save(deps.storage, nft_contract_address.as_str(), &collection_info)?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "set_collection_info")
+            .add_attribute("nft_contract_address", nft_contract_address)
+            .add_attribute("verified", collection_info.verified.to_string())
+    )
+}
+
+pub fn remove_collection_info(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    if !COLLECTIONS.has(deps.storage, nft_contract_address.as_str()) {
+        return Err(ContractError::CollectionNotFound {  });
+    }
+
+    COLLECTIONS.remove(deps.storage, nft_contract_address.as_str());
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "remove_collection_info")
+            .add_attribute("nft_contract_address", nft_contract_address)
+    )
+}
+
+pub fn update_curation_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    curation_mode: bool,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<Config, ContractError> {
+        config.curation_mode = curation_mode;
+        Ok(config)
+    })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_curation_mode")
+            .add_attribute("curation_mode", curation_mode.to_string())
+    )
+}
+
+// collection minter or owner only, without unfreeze_at the collection stays frozen until unfrozen
+pub fn freeze_collection_trading(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    nft_contract_address: String,
+    unfreeze_at: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    if !is_collection_creator(&deps, &nft_contract_address, &info.sender) {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    if unfreeze_at.is_some_and(|unfreeze_at| unfreeze_at <= env.block.time) {
+        return Err(ContractError::InvalidUnfreezeTime {  });
+    }
+
+    COLLECTION_FREEZES.save(deps.storage, nft_contract_address.as_str(), &CollectionFreeze { unfreeze_at })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "freeze_collection_trading")
+            .add_attribute("nft_contract_address", nft_contract_address)
+            .add_attribute("unfreeze_at", unfreeze_at.map_or("none".to_string(), |unfreeze_at| unfreeze_at.to_string()))
+    )
+}
+
+// collection minter or owner only
+pub fn unfreeze_collection_trading(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    if !is_collection_creator(&deps, &nft_contract_address, &info.sender) {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    COLLECTION_FREEZES.remove(deps.storage, nft_contract_address.as_str());
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "unfreeze_collection_trading")
+            .add_attribute("nft_contract_address", nft_contract_address)
+    )
+}
+
+// frozen collections and, in curation mode, unverified collections can't be listed, bid on or sold, cancels always work
+pub fn ensure_collection_tradable(storage: &dyn Storage, env: &Env, nft_contract_address: &Addr) -> Result<(), ContractError> {
+    if let Some(collection_freeze) = COLLECTION_FREEZES.may_load(storage, nft_contract_address.as_str())? {
+        if collection_freeze.is_frozen(env.block.time) {
+            return Err(ContractError::CollectionFrozen {  });
+        }
+    }
+
+    let curation_mode = CONFIG.may_load(storage)?.is_some_and(|config| config.curation_mode);
+    if !curation_mode {
+        return Ok(());
+    }
+
+    let verified = COLLECTIONS.may_load(storage, nft_contract_address.as_str())?.is_some_and(|collection_info| collection_info.verified);
+    if !verified {
+        return Err(ContractError::CollectionNotVerified {  });
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use cosmwasm_std::{from_json, DepsMut, Empty, Env, MessageInfo, Response};
+use ::cw20::Cw20ReceiveMsg;
+
+use crate::{execute::{admin::ensure_admin, bidding::place_bid, collection_bidding::place_collection_bid, listing::purchase_listing, sudo::ensure_not_paused_or_blocked}, msg::ReceiveMsg, state::{PaymentAsset, CW20_ALLOWLIST}, ContractError};
+
+
+// info.sender is the cw20 contract, the tokens were already transferred to this contract
+pub fn receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    cw20_receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    if !CW20_ALLOWLIST.has(deps.storage, info.sender.as_str()) {
+        return Err(ContractError::Cw20NotAllowed {  });
+    }
+
+    let payment_asset = PaymentAsset::Cw20 { contract_address: info.sender };
+    let sender = deps.api.addr_validate(cw20_receive_msg.sender.as_str())?;
+    ensure_not_paused_or_blocked(&deps, sender.as_str())?;
+    let amount = cw20_receive_msg.amount;
+
+    match from_json(&cw20_receive_msg.msg)? {
+        ReceiveMsg::BuyListing { nft_contract_address, token_id, referrer } => purchase_listing(deps, env, sender, payment_asset, amount, nft_contract_address, token_id, referrer),
+        ReceiveMsg::Bid { price, nft_contract_address, token_id } => place_bid(deps, env, sender, payment_asset, amount, price, nft_contract_address, token_id, None),
+        ReceiveMsg::CollectionBid { prices, nft_contract_address } => place_collection_bid(deps, env, sender, payment_asset, amount, prices, nft_contract_address, None),
+    }
+}
+
+pub fn allow_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let contract_address = deps.api.addr_validate(contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidCw20ContractAddress {  })?;
+
+    if CW20_ALLOWLIST.has(deps.storage, contract_address.as_str()) {
+        return Err(ContractError::Cw20AlreadyAllowed {  });
+    }
+
+    CW20_ALLOWLIST.save(deps.storage, contract_address.as_str(), &Empty {})?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "allow_cw20")
+            .add_attribute("contract_address", contract_address)
+    )
+}
+
+pub fn disallow_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let contract_address = deps.api.addr_validate(contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidCw20ContractAddress {  })?;
+
+    if !CW20_ALLOWLIST.has(deps.storage, contract_address.as_str()) {
+        return Err(ContractError::Cw20NotAllowed {  });
+    }
+
+    CW20_ALLOWLIST.remove(deps.storage, contract_address.as_str());
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "disallow_cw20")
+            .add_attribute("contract_address", contract_address)
+    )
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::{DepsMut, Empty, MessageInfo, Response};
+
+use crate::{execute::admin::ensure_admin, state::{PaymentAsset, DENOM_ALLOWLIST}, ContractError};
+
+
+pub fn allow_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    // usei is always allowed
+    if denom.is_empty() || denom == PaymentAsset::default().id() {
+        return Err(ContractError::InvalidDenom {  });
+    }
+
+    if DENOM_ALLOWLIST.has(deps.storage, denom.as_str()) {
+        return Err(ContractError::DenomAlreadyAllowed {  });
+    }
+
+    DENOM_ALLOWLIST.save(deps.storage, denom.as_str(), &Empty {})?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "allow_denom")
+            .add_attribute("denom", denom)
+    )
+}
+
+pub fn disallow_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    if !DENOM_ALLOWLIST.has(deps.storage, denom.as_str()) {
+        return Err(ContractError::DenomNotAllowed {  });
+    }
+
+    DENOM_ALLOWLIST.remove(deps.storage, denom.as_str());
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "disallow_denom")
+            .add_attribute("denom", denom)
+    )
+}
@@ -0,0 +1,169 @@
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::Bound;
+
+use crate::{execute::admin::ensure_admin, state::{CollectionFeeOverride, FeeSchedule, COLLECTION_FEE_OVERRIDES, FEE_SCHEDULE, TRADE_VOLUMES}, ContractError};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const VOLUME_WINDOW_DAYS: u64 = 30;
+const MAX_FEE: Decimal = Decimal::percent(10);
+
+pub enum FeeRole {
+    Maker,
+    Taker,
+}
+
+impl FeeRole {
+    // role of the other side of the trade
+    pub fn counterparty(&self) -> FeeRole {
+        match self {
+            FeeRole::Maker => FeeRole::Taker,
+            FeeRole::Taker => FeeRole::Maker,
+        }
+    }
+}
+
+
+pub fn update_fee_schedule(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_schedule: FeeSchedule,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    save_fee_schedule(deps.storage, fee_schedule)
+}
+
+// shared by the admin and governance through sudo
+pub fn save_fee_schedule(storage: &mut dyn Storage, fee_schedule: FeeSchedule) -> Result<Response, ContractError> {
+    let is_fee_too_high = fee_schedule.maker_fee > MAX_FEE
+        || fee_schedule.taker_fee > MAX_FEE
+        || fee_schedule.tiers.iter().any(|tier| tier.maker_fee > MAX_FEE || tier.taker_fee > MAX_FEE);
+    let is_tiers_unsorted = fee_schedule.tiers.windows(2).any(|tiers| tiers[0].min_volume >= tiers[1].min_volume);
+    if is_fee_too_high || is_tiers_unsorted {
+        return Err(ContractError::InvalidFeeSchedule {  });
+    }
+
+    FEE_SCHEDULE.save(storage, &fee_schedule)?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_fee_schedule")
+            .add_attribute("maker_fee", fee_schedule.maker_fee.to_string())
+            .add_attribute("taker_fee", fee_schedule.taker_fee.to_string())
+            .add_attribute("tiers", fee_schedule.tiers.len().to_string())
+    )
+}
+
+pub fn set_collection_fee_override(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+    fee: Decimal,
+    starts_at: Option<Timestamp>,
+    ends_at: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let is_window_empty = matches!((starts_at, ends_at), (Some(starts_at), Some(ends_at)) if starts_at >= ends_at);
+    if fee > MAX_FEE || is_window_empty {
+        return Err(ContractError::InvalidCollectionFeeOverride {  });
+    }
+
+    let collection_fee_override = CollectionFeeOverride {
+        fee,
+        starts_at,
+        ends_at,
+    };
+    COLLECTION_FEE_OVERRIDES.save(deps.storage, nft_contract_address.as_str(), &collection_fee_override)?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "set_collection_fee_override")
+            .add_attribute("nft_contract_address", nft_contract_address)
+            .add_attribute("fee", fee.to_string())
+            .add_attribute("starts_at", starts_at.map_or(String::new(), |starts_at| starts_at.to_string()))
+            .add_attribute("ends_at", ends_at.map_or(String::new(), |ends_at| ends_at.to_string()))
+    )
+}
+
+pub fn remove_collection_fee_override(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    if !COLLECTION_FEE_OVERRIDES.has(deps.storage, nft_contract_address.as_str()) {
+        return Err(ContractError::CollectionFeeOverrideNotFound {  });
+    }
+    COLLECTION_FEE_OVERRIDES.remove(deps.storage, nft_contract_address.as_str());
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "remove_collection_fee_override")
+            .add_attribute("nft_contract_address", nft_contract_address)
+    )
+}
+
+// (maker fee, taker fee) of the account after applying its volume tier
+pub fn effective_fees(storage: &dyn Storage, env: &Env, account: &str) -> StdResult<(Decimal, Decimal)> {
+    let fee_schedule = FEE_SCHEDULE.may_load(storage)?.unwrap_or_default();
+    let volume = trailing_volume(storage, env, account)?;
+
+    let fees = match fee_schedule.tiers.iter().rev().find(|tier| volume >= tier.min_volume) {
+        Some(tier) => (tier.maker_fee, tier.taker_fee),
+        None => (fee_schedule.maker_fee, fee_schedule.taker_fee),
+    };
+    Ok(fees)
+}
+
+// an active collection override wins over the account rates, it is the total fee of the sale and paid by the taker alone
+pub fn platform_fee_rate(storage: &dyn Storage, env: &Env, nft_contract_address: &Addr, account: &str, fee_role: FeeRole) -> StdResult<Decimal> {
+    if let Some(collection_fee_override) = COLLECTION_FEE_OVERRIDES.may_load(storage, nft_contract_address.as_str())? {
+        if collection_fee_override.is_active(env.block.time) {
+            return match fee_role {
+                FeeRole::Maker => Ok(Decimal::zero()),
+                FeeRole::Taker => Ok(collection_fee_override.fee),
+            };
+        }
+    }
+
+    let (maker_fee, taker_fee) = effective_fees(storage, env, account)?;
+    match fee_role {
+        FeeRole::Maker => Ok(maker_fee),
+        FeeRole::Taker => Ok(taker_fee),
+    }
+}
+
+// usei traded by the account in the last 30 days including today
+pub fn trailing_volume(storage: &dyn Storage, env: &Env, account: &str) -> StdResult<Uint128> {
+    let today = env.block.time.seconds() / SECONDS_PER_DAY;
+    let first_day = today.saturating_sub(VOLUME_WINDOW_DAYS - 1);
+
+    TRADE_VOLUMES
+        .prefix(account)
+        .range(storage, Some(Bound::inclusive(first_day)), None, Order::Ascending)
+        .try_fold(Uint128::zero(), |volume, item| {
+            let (_, day_volume) = item?;
+            Ok(volume.checked_add(day_volume)?)
+        })
+}
+
+pub fn record_trade_volume(storage: &mut dyn Storage, env: &Env, account: &str, amount: Uint128) -> StdResult<()> {
+    let today = env.block.time.seconds() / SECONDS_PER_DAY;
+    TRADE_VOLUMES.update(storage, (account, today), |volume| -> StdResult<Uint128> {
+        Ok(volume.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    // the day that just left the window is not needed anymore
+    if today >= VOLUME_WINDOW_DAYS {
+        TRADE_VOLUMES.remove(storage, (account, today - VOLUME_WINDOW_DAYS));
+    }
+    Ok(())
+}
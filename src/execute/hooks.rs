@@ -0,0 +1,97 @@
+use cosmwasm_std::{to_json_binary, DepsMut, Empty, MessageInfo, Order, Response, Storage, SubMsg, WasmMsg};
+
+use crate::{execute::admin::ensure_admin, msg::{HookExecuteMsg, TradeHookMsg}, state::{Config, CONFIG, DEFAULT_HOOK_GAS_LIMIT, HOOKS}, ContractError};
+
+pub const HOOK_REPLY_ID: u64 = 1;
+
+
+pub fn add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let contract_address = deps.api.addr_validate(contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidHookContractAddress {  })?;
+
+    if HOOKS.has(deps.storage, contract_address.as_str()) {
+        return Err(ContractError::HookAlreadyExists {  });
+    }
+    HOOKS.save(deps.storage, contract_address.as_str(), &Empty {})?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("contract_address", contract_address)
+    )
+}
+
+pub fn remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    let contract_address = deps.api.addr_validate(contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidHookContractAddress {  })?;
+
+    if !HOOKS.has(deps.storage, contract_address.as_str()) {
+        return Err(ContractError::HookNotFound {  });
+    }
+    HOOKS.remove(deps.storage, contract_address.as_str());
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "remove_hook")
+            .add_attribute("contract_address", contract_address)
+    )
+}
+
+pub fn update_hook_gas_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    hook_gas_limit: u64,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info)?;
+
+    if hook_gas_limit == 0 {
+        return Err(ContractError::InvalidHookGasLimit {  });
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> Result<Config, ContractError> {
+        config.hook_gas_limit = hook_gas_limit;
+        Ok(config)
+    })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_hook_gas_limit")
+            .add_attribute("hook_gas_limit", hook_gas_limit.to_string())
+    )
+}
+
+// notifies every registered hook, a failing hook is only logged in reply so the trade is not reverted
+pub fn add_trade_hook_msgs(
+    storage: &dyn Storage,
+    trade_hook_msg: TradeHookMsg,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let msg = to_json_binary(&HookExecuteMsg::TradeHook(trade_hook_msg))?;
+    let hook_gas_limit = CONFIG.may_load(storage)?.map_or(DEFAULT_HOOK_GAS_LIMIT, |config| config.hook_gas_limit);
+
+    let hook_msgs = HOOKS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|contract_address| -> Result<SubMsg, ContractError> {
+            let execute_hook_msg = WasmMsg::Execute {
+                contract_addr: contract_address?,
+                msg: msg.clone(),
+                funds: vec![]
+            };
+            Ok(SubMsg::reply_on_error(execute_hook_msg, HOOK_REPLY_ID).with_gas_limit(hook_gas_limit))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(response.add_submessages(hook_msgs))
+}
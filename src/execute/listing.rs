@@ -1,28 +1,68 @@
-use std::str::FromStr;
+use cosmwasm_std::{Addr, DepsMut, Empty, Env, MessageInfo, Response, Storage, Timestamp, Uint128};
 
-use cosmwasm_std::{coins, to_json_binary, BankMsg, Decimal, DepsMut, Env, MessageInfo, QueryRequest, Response, Uint128, WasmMsg, WasmQuery};
-use cw721::OwnerOfResponse;
-
-use crate::{ContractError, state::{NftListing, NFT_LISTINGS, PLATFORM_FEE_RECEIVER}, utils::{parse_decimal, query_check_royalties, query_royalty_info}};
+use crate::{ContractError, execute::{blocklist::ensure_nft_not_blocked, collection_bidding::take_best_collection_bid, collections::ensure_collection_tradable, fees::FeeRole, hooks::add_trade_hook_msgs}, msg::{TradeHookAction, TradeHookMsg}, state::{ListingCustody, ListingReservation, NftListing, PaymentAsset, ReservationExpiry, NFT_BUNDLED_ITEMS, NFT_LISTINGS, NFT_RESERVED_LISTINGS, NFT_SWAP_ITEMS}, utils::{add_sale_payout_msgs, is_marketplace_approved, query_nft_owner, transfer_nft_msg, validate_cw20_payment_asset, validate_native_payment_asset, validate_referrer, verify_nft_in_escrow}};
 
+#[allow(clippy::too_many_arguments)]
 pub fn list(
-    deps: DepsMut,
+    mut deps: DepsMut,
     info: MessageInfo,
     env: Env,
-    price: String,
+    price: Uint128,
     nft_contract_address: String,
     token_id: String,
+    reserved_for: Option<String>,
+    reservation_expires_at: Option<Timestamp>,
+    on_reservation_expiry: Option<ReservationExpiry>,
+    fill_collection_bid: Option<bool>,
+    escrowless: Option<bool>,
+    denom: Option<String>,
+    cw20_contract_address: Option<String>,
 ) -> Result<Response, ContractError> {
-    let price = Decimal::from_str(price.as_str())
-        .map_err(|_e| ContractError::InvalidPrice {  })?;
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+    ensure_nft_not_blocked(deps.storage, &nft_contract_address, token_id.as_str())?;
+    let payment_asset = match (denom, cw20_contract_address) {
+        (Some(_), Some(_)) => return Err(ContractError::MultiplePaymentAssets {  }),
+        (None, Some(cw20_contract_address)) => validate_cw20_payment_asset(deps.storage, deps.api, cw20_contract_address)?,
+        (denom, None) => validate_native_payment_asset(deps.storage, denom)?,
+    };
+
+    let reservation = match reserved_for {
+        Some(reserved_for) => {
+            let buyer = deps.api.addr_validate(reserved_for.as_str())
+                .map_err(|_e| ContractError::InvalidReservedBuyer {  })?;
+            if buyer == info.sender {
+                return Err(ContractError::InvalidReservedBuyer {  });
+            }
+            if reservation_expires_at.is_some_and(|expires_at| expires_at <= env.block.time) {
+                return Err(ContractError::InvalidReservationExpiry {  });
+            }
+            if fill_collection_bid.unwrap_or(false) {
+                return Err(ContractError::ReservedListingCantFillCollectionBid {  });
+            }
+            Some(ListingReservation {
+                buyer,
+                expires_at: reservation_expires_at,
+                on_expiry: on_reservation_expiry.unwrap_or(ReservationExpiry::Public),
+            })
+        },
+        None => {
+            if reservation_expires_at.is_some() || on_reservation_expiry.is_some() {
+                return Err(ContractError::InvalidReservedBuyer {  });
+            }
+            None
+        }
+    };
 
     let nft_listing = NftListing {
         lister: info.sender,
         price,
         nft_contract_address,
-        token_id
+        token_id,
+        reservation,
+        custody: if escrowless.unwrap_or(false) { ListingCustody::Approval } else { ListingCustody::Escrow },
+        payment_asset,
     };
 
     let key = (nft_listing.nft_contract_address.as_str(), nft_listing.token_id.as_str());
@@ -32,6 +72,75 @@ pub fn list(
         return Err(ContractError::ListingAlreadyExists {  });
     }
 
+    if NFT_BUNDLED_ITEMS.has(deps.storage, key) {
+        return Err(ContractError::NftAlreadyInBundle {  });
+    }
+
+    if NFT_SWAP_ITEMS.has(deps.storage, key) {
+        return Err(ContractError::NftAlreadyInSwap {  });
+    }
+
+    match nft_listing.custody {
+        // verify that escrow has the NFT
+        ListingCustody::Escrow => verify_nft_in_escrow(&deps, &env, &nft_listing.nft_contract_address, nft_listing.token_id.clone())?,
+        // verify that lister owns the NFT and approved the contract to transfer it
+        ListingCustody::Approval => {
+            let owner = query_nft_owner(&deps, &nft_listing.nft_contract_address, nft_listing.token_id.clone())?;
+            if owner != nft_listing.lister {
+                return Err(ContractError::NftNotOwnedByLister {  });
+            }
+            if !is_marketplace_approved(&deps, &env, &nft_listing.nft_contract_address, nft_listing.token_id.clone(), owner) {
+                return Err(ContractError::NftNotApproved {  });
+            }
+        }
+    }
+
+    // sell straight to the best collection bid when it meets the listing price
+    if fill_collection_bid.unwrap_or(false) {
+        let best_collection_bid = take_best_collection_bid(deps.storage, &nft_listing.nft_contract_address, nft_listing.price, &nft_listing.lister, &nft_listing.payment_asset)?;
+        if let Some((bidder, price)) = best_collection_bid {
+            // transfer nft from escrow or lister wallet to bidder
+            let transfer_nft_msg = transfer_nft_msg(&nft_listing.nft_contract_address, bidder.to_string(), nft_listing.token_id.clone())?;
+
+            let response = Response::new()
+                .add_message(transfer_nft_msg)
+                .add_attribute("action", "list")
+                .add_attribute("price", nft_listing.price.to_string())
+                .add_attribute("lister", nft_listing.lister.clone())
+                .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
+                .add_attribute("token_id", nft_listing.token_id.clone())
+                .add_attribute("filled_collection_bid_price", price.to_string())
+                .add_attribute("bidder", bidder.to_string());
+
+            let response = add_sale_payout_msgs(
+                &mut deps,
+                &env,
+                &nft_listing.nft_contract_address,
+                nft_listing.token_id.clone(),
+                price,
+                &nft_listing.lister,
+                FeeRole::Taker,
+                &bidder,
+                &nft_listing.payment_asset,
+                None,
+                None,
+                response,
+            )?;
+
+            let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+                action: TradeHookAction::Sale,
+                collection: nft_listing.nft_contract_address.to_string(),
+                token_id: nft_listing.token_id,
+                price,
+                payment_asset: nft_listing.payment_asset.clone(),
+                buyer: Some(bidder.to_string()),
+                seller: nft_listing.lister.to_string(),
+            }, response)?;
+
+            return Ok(response);
+        }
+    }
+
     NFT_LISTINGS.save(
         deps.storage,
         key,
@@ -39,109 +148,142 @@ pub fn list(
     )
         .map_err(|_e| ContractError::ErrorCreatingNewListing {  })?;
 
-    // verify that escrow has the NFT
-    let cw721_query_owner_msg = cw721::Cw721QueryMsg::OwnerOf {
-        token_id: nft_listing.token_id.clone(),
-        include_expired: Some(false),
-    };
+    if let Some(reservation) = &nft_listing.reservation {
+        NFT_RESERVED_LISTINGS.save(deps.storage, (reservation.buyer.as_str(), key.0, key.1), &Empty {})?;
+    }
 
-    let cw721_query = QueryRequest::Wasm(
-        WasmQuery::Smart {
-            contract_addr: nft_listing.nft_contract_address.to_string(),
-            msg: to_json_binary(&cw721_query_owner_msg)?
-        }
-    );
+    let mut response = Response::new()
+        .add_attribute("action", "list")
+        .add_attribute("price", nft_listing.price.to_string())
+        .add_attribute("lister", nft_listing.lister.clone())
+        .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
+        .add_attribute("token_id", nft_listing.token_id.clone())
+        .add_attribute("escrowless", (nft_listing.custody == ListingCustody::Approval).to_string());
 
-    let cw721_query_response: OwnerOfResponse = deps.querier.query(&cw721_query)?;
-    if cw721_query_response.owner != env.contract.address.to_string() {
-        return Err(ContractError::NftNotInEscrow {  });
+    if let Some(reservation) = nft_listing.reservation {
+        response = response.add_attribute("reserved_for", reservation.buyer);
     }
 
-    Ok(
-        Response::new()
-            .add_attribute("action", "list")
-            .add_attribute("price", parse_decimal(nft_listing.price)?.to_string())
-            .add_attribute("lister", nft_listing.lister)
-            .add_attribute("nft_contract_address", nft_listing.nft_contract_address)
-            .add_attribute("token_id", nft_listing.token_id)
-    )
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Listing,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price: nft_listing.price,
+        payment_asset: nft_listing.payment_asset.clone(),
+        buyer: None,
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
+    Ok(response)
 }
 
 pub fn buy_listing(
     deps: DepsMut,
     info: MessageInfo,
+    env: Env,
+    nft_contract_address: String,
+    token_id: String,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    // paid in the native denom of the listing, cw20 listings are bought through Receive
+    let payment_asset = NFT_LISTINGS.may_load(deps.storage, (nft_contract_address.as_str(), token_id.as_str()))?
+        .map_or(PaymentAsset::default(), |nft_listing| nft_listing.payment_asset);
+    if !matches!(payment_asset, PaymentAsset::Native { .. }) {
+        return Err(ContractError::PaymentAssetMismatch {  });
+    }
+
+    let sent_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == payment_asset.id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    purchase_listing(deps, env, info.sender, payment_asset, sent_amount, nft_contract_address, token_id, referrer)
+}
+
+// buyer pays price, taker fee and royalties in the payment asset of the listing
+#[allow(clippy::too_many_arguments)]
+pub fn purchase_listing(
+    mut deps: DepsMut,
+    env: Env,
+    buyer: Addr,
+    payment_asset: PaymentAsset,
+    sent_amount: Uint128,
     nft_contract_address: String,
     token_id: String,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+    let referrer = validate_referrer(deps.api, referrer, &buyer)?;
 
     let key = (nft_contract_address.as_str(), token_id.as_str());
 
     let nft_listing = NFT_LISTINGS.load(deps.storage, key)
         .map_err(|_e| ContractError::NftListingNotFound {  })?;
 
-    NFT_LISTINGS.remove(deps.storage, key);
+    if nft_listing.payment_asset != payment_asset {
+        return Err(ContractError::PaymentAssetMismatch {  });
+    }
 
-    // transfer nft from escrow to buyer
-    let cw721_transfer_nft_msg = cw721::Cw721ExecuteMsg::TransferNft {
-        recipient: info.sender.to_string(),
-        token_id: nft_listing.token_id.clone()
-    };
-    let transfer_nft_msg = WasmMsg::Execute {
-        contract_addr: nft_listing.nft_contract_address.to_string(),
-        msg: to_json_binary(&cw721_transfer_nft_msg)?,
-        funds: vec![]
-    };
+    if let Some(reservation) = &nft_listing.reservation {
+        if !reservation.is_expired(env.block.time) {
+            if buyer != reservation.buyer {
+                return Err(ContractError::ListingReservedForAnotherBuyer {  });
+            }
+        } else if reservation.on_expiry == ReservationExpiry::Return {
+            return Err(ContractError::ListingReservationExpired {  });
+        }
+    }
 
-    // transfer sei to lister
-    let transfer_sei_msg = BankMsg::Send {
-        to_address: nft_listing.lister.to_string(),
-        amount: coins(parse_decimal(nft_listing.price)?.u128(), "usei")
-    };
+    // escrowless listing has to be backed by the lister wallet at purchase time
+    if is_listing_stale(&deps, &env, &nft_listing)? {
+        return Err(ContractError::StaleListing {  });
+    }
 
-    // pay platform fee
-    let platform_fee = nft_listing.price * Decimal::percent(2);
-    let pay_platform_fee_msg = BankMsg::Send {
-        to_address: PLATFORM_FEE_RECEIVER.to_string(),
-        amount: coins(parse_decimal(platform_fee)?.u128(), "usei")
-    };
+    remove_nft_listing(deps.storage, &nft_listing);
 
-    let mut response = Response::new()
+    let price = nft_listing.price;
+
+    // transfer nft from escrow or lister wallet to buyer
+    let transfer_nft_msg = transfer_nft_msg(&nft_listing.nft_contract_address, buyer.to_string(), nft_listing.token_id.clone())?;
+
+    let response = Response::new()
         .add_message(transfer_nft_msg)
-        .add_message(transfer_sei_msg)
-        .add_message(pay_platform_fee_msg)
         .add_attribute("action", "buy_listing")
-        .add_attribute("price", parse_decimal(nft_listing.price)?.to_string())
-        .add_attribute("lister", nft_listing.lister)
-        .add_attribute("buyer", info.sender.to_string())
+        .add_attribute("price", price.to_string())
+        .add_attribute("lister", nft_listing.lister.clone())
+        .add_attribute("buyer", buyer.to_string())
         .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
         .add_attribute("token_id", nft_listing.token_id.clone());
 
-    // pay royalties
-    let check_royalties_response_result = query_check_royalties(&deps, nft_listing.nft_contract_address.to_string());
-    match check_royalties_response_result {
-        Ok(check_royalties_response) => {
-            if check_royalties_response.royalty_payments {
-                let royalty_info_response = query_royalty_info(
-                    &deps,
-                    nft_listing.nft_contract_address.to_string(),
-                    nft_listing.token_id.clone(),
-                    parse_decimal(nft_listing.price)?,
-                )?;
-                if !royalty_info_response.address.is_empty() && royalty_info_response.royalty_amount > Uint128::zero() {
-                    let pay_royalties_msg = BankMsg::Send {
-                        to_address: royalty_info_response.address,
-                        amount: coins(royalty_info_response.royalty_amount.u128(), "usei")
-                    };
-                    response = response.add_message(pay_royalties_msg);
-                }
-            }
-        },
-        // if there is an error that means the nft contract does not support royalties
-        Err(_e) => {}
-    }
-    
+    // lister pays the maker fee out of the price, buyer pays the taker fee and royalties on top
+    let response = add_sale_payout_msgs(
+        &mut deps,
+        &env,
+        &nft_listing.nft_contract_address,
+        nft_listing.token_id.clone(),
+        nft_listing.price,
+        &nft_listing.lister,
+        FeeRole::Maker,
+        &buyer,
+        &payment_asset,
+        Some(sent_amount),
+        referrer.as_ref(),
+        response,
+    )?;
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Sale,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price,
+        payment_asset,
+        buyer: Some(buyer.to_string()),
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
     Ok(response)
 }
 
@@ -159,31 +301,36 @@ pub fn cancel_listing(
     let nft_listing = NFT_LISTINGS.load(deps.storage, key)
         .map_err(|_e| ContractError::NftListingNotFound {  })?;
 
-    NFT_LISTINGS.remove(deps.storage, key);
+    remove_nft_listing(deps.storage, &nft_listing);
 
     if info.sender != nft_listing.lister {
         return Err(ContractError::Unauthorized {  });
     }
 
-    // transfer nft from escrow back to lister
-    let cw721_transfer_nft_msg = cw721::Cw721ExecuteMsg::TransferNft {
-        recipient: info.sender.to_string(),
-        token_id: nft_listing.token_id.clone()
-    };
-    let transfer_nft_msg = WasmMsg::Execute {
-        contract_addr: nft_listing.nft_contract_address.to_string(),
-        msg: to_json_binary(&cw721_transfer_nft_msg)?,
-        funds: vec![]
-    };
-
-    let response = Response::new()
-        .add_message(transfer_nft_msg)
+    let mut response = Response::new()
         .add_attribute("action", "cancel_listing")
-        .add_attribute("price", parse_decimal(nft_listing.price)?.to_string())
-        .add_attribute("lister", nft_listing.lister)
+        .add_attribute("price", nft_listing.price.to_string())
+        .add_attribute("lister", nft_listing.lister.clone())
         .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
         .add_attribute("token_id", nft_listing.token_id.clone());
 
+    // transfer nft from escrow back to lister, escrowless nft never left the lister wallet
+    if nft_listing.custody == ListingCustody::Escrow {
+        ensure_nft_not_blocked(deps.storage, &nft_listing.nft_contract_address, nft_listing.token_id.as_str())?;
+        let transfer_nft_msg = transfer_nft_msg(&nft_listing.nft_contract_address, info.sender.to_string(), nft_listing.token_id.clone())?;
+        response = response.add_message(transfer_nft_msg);
+    }
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Cancel,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price: nft_listing.price,
+        payment_asset: nft_listing.payment_asset.clone(),
+        buyer: None,
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
     Ok(response)
 }
 
@@ -192,10 +339,8 @@ pub fn delist(
     info: MessageInfo,
     nft_contract_address: String,
     token_id: String,
-    new_price: String,
+    new_price: Uint128,
 ) -> Result<Response, ContractError> {
-    let new_price = Decimal::from_str(new_price.as_str())
-        .map_err(|_e| ContractError::InvalidPrice {  })?;
     let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
         .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
 
@@ -211,7 +356,10 @@ pub fn delist(
                     price: new_price,
                     lister: nft_listing.lister,
                     nft_contract_address: nft_listing.nft_contract_address,
-                    token_id: nft_listing.token_id
+                    token_id: nft_listing.token_id,
+                    reservation: nft_listing.reservation,
+                    custody: nft_listing.custody,
+                    payment_asset: nft_listing.payment_asset,
                 })
             },
             None => Err(ContractError::NftListingNotFound {  })
@@ -220,10 +368,177 @@ pub fn delist(
 
     let response = Response::new()
         .add_attribute("action", "delist")
-        .add_attribute("new_price", parse_decimal(nft_listing.price)?.to_string())
-        .add_attribute("lister", nft_listing.lister)
+        .add_attribute("new_price", nft_listing.price.to_string())
+        .add_attribute("lister", nft_listing.lister.clone())
         .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
         .add_attribute("token_id", nft_listing.token_id.clone());
 
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Listing,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price: nft_listing.price,
+        payment_asset: nft_listing.payment_asset.clone(),
+        buyer: None,
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
     Ok(response)
-}
\ No newline at end of file
+}
+
+pub fn return_expired_listing(
+    deps: DepsMut,
+    env: Env,
+    nft_contract_address: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let key = (nft_contract_address.as_str(), token_id.as_str());
+
+    let nft_listing = NFT_LISTINGS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftListingNotFound {  })?;
+
+    match &nft_listing.reservation {
+        Some(reservation) if reservation.on_expiry == ReservationExpiry::Return && reservation.is_expired(env.block.time) => {},
+        _ => return Err(ContractError::ListingReservationNotExpired {  }),
+    }
+
+    remove_nft_listing(deps.storage, &nft_listing);
+
+    let mut response = Response::new()
+        .add_attribute("action", "return_expired_listing")
+        .add_attribute("price", nft_listing.price.to_string())
+        .add_attribute("lister", nft_listing.lister.clone())
+        .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
+        .add_attribute("token_id", nft_listing.token_id.clone());
+
+    // transfer nft from escrow back to lister, escrowless nft never left the lister wallet
+    if nft_listing.custody == ListingCustody::Escrow {
+        ensure_nft_not_blocked(deps.storage, &nft_listing.nft_contract_address, nft_listing.token_id.as_str())?;
+        let transfer_nft_msg = transfer_nft_msg(&nft_listing.nft_contract_address, nft_listing.lister.to_string(), nft_listing.token_id.clone())?;
+        response = response.add_message(transfer_nft_msg);
+    }
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Cancel,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price: nft_listing.price,
+        payment_asset: nft_listing.payment_asset.clone(),
+        buyer: None,
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
+    Ok(response)
+}
+
+pub fn invalidate_stale_listing(
+    deps: DepsMut,
+    env: Env,
+    nft_contract_address: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let key = (nft_contract_address.as_str(), token_id.as_str());
+
+    let nft_listing = NFT_LISTINGS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftListingNotFound {  })?;
+
+    if !is_listing_stale(&deps, &env, &nft_listing)? {
+        return Err(ContractError::ListingNotStale {  });
+    }
+
+    remove_nft_listing(deps.storage, &nft_listing);
+
+    let response = Response::new()
+        .add_attribute("action", "invalidate_stale_listing")
+        .add_attribute("price", nft_listing.price.to_string())
+        .add_attribute("lister", nft_listing.lister.clone())
+        .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
+        .add_attribute("token_id", nft_listing.token_id.clone());
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Cancel,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price: nft_listing.price,
+        payment_asset: nft_listing.payment_asset.clone(),
+        buyer: None,
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
+    Ok(response)
+}
+
+pub fn force_return_listing(
+    deps: DepsMut,
+    nft_contract_address: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let key = (nft_contract_address.as_str(), token_id.as_str());
+
+    let nft_listing = NFT_LISTINGS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftListingNotFound {  })?;
+
+    remove_nft_listing(deps.storage, &nft_listing);
+
+    let mut response = Response::new()
+        .add_attribute("action", "force_return_listing")
+        .add_attribute("price", nft_listing.price.to_string())
+        .add_attribute("lister", nft_listing.lister.clone())
+        .add_attribute("nft_contract_address", nft_listing.nft_contract_address.clone())
+        .add_attribute("token_id", nft_listing.token_id.clone());
+
+    // transfer nft from escrow back to lister, escrowless nft never left the lister wallet
+    if nft_listing.custody == ListingCustody::Escrow {
+        ensure_nft_not_blocked(deps.storage, &nft_listing.nft_contract_address, nft_listing.token_id.as_str())?;
+        let transfer_nft_msg = transfer_nft_msg(&nft_listing.nft_contract_address, nft_listing.lister.to_string(), nft_listing.token_id.clone())?;
+        response = response.add_message(transfer_nft_msg);
+    }
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Cancel,
+        collection: nft_listing.nft_contract_address.to_string(),
+        token_id: nft_listing.token_id,
+        price: nft_listing.price,
+        payment_asset: nft_listing.payment_asset.clone(),
+        buyer: None,
+        seller: nft_listing.lister.to_string(),
+    }, response)?;
+
+    Ok(response)
+}
+
+// escrowless listing is stale once the lister no longer owns the nft or revoked the contract approval
+pub fn is_listing_stale(deps: &DepsMut, env: &Env, nft_listing: &NftListing) -> Result<bool, ContractError> {
+    if nft_listing.custody == ListingCustody::Escrow {
+        return Ok(false);
+    }
+
+    let owner = match query_nft_owner(deps, &nft_listing.nft_contract_address, nft_listing.token_id.clone()) {
+        Ok(owner) => owner,
+        // burned nft has no owner anymore
+        Err(_e) => return Ok(true),
+    };
+    if owner != nft_listing.lister {
+        return Ok(true);
+    }
+
+    Ok(!is_marketplace_approved(deps, env, &nft_listing.nft_contract_address, nft_listing.token_id.clone(), owner))
+}
+
+// removes the listing together with its reserved buyer index
+pub fn remove_nft_listing(storage: &mut dyn Storage, nft_listing: &NftListing) {
+    let key = (nft_listing.nft_contract_address.as_str(), nft_listing.token_id.as_str());
+    NFT_LISTINGS.remove(storage, key);
+    if let Some(reservation) = &nft_listing.reservation {
+        NFT_RESERVED_LISTINGS.remove(storage, (reservation.buyer.as_str(), key.0, key.1));
+    }
+}
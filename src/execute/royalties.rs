@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use cosmwasm_std::{Decimal, DepsMut, MessageInfo, Response};
+
+use crate::{state::{RoyaltyRecipient, ROYALTY_REGISTRY}, utils::is_collection_creator, ContractError};
+
+const MAX_ROYALTY_RECIPIENTS: usize = 10;
+const MAX_TOTAL_ROYALTY: Decimal = Decimal::percent(25);
+
+
+pub fn set_collection_royalties(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+    recipients: Vec<(String, Decimal)>,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    if !is_collection_creator(&deps, &nft_contract_address, &info.sender) {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    if recipients.is_empty() || recipients.len() > MAX_ROYALTY_RECIPIENTS {
+        return Err(ContractError::InvalidRoyaltyRecipients {  });
+    }
+
+    let mut addresses = HashSet::new();
+    let mut total_share = Decimal::zero();
+    let mut royalty_recipients = vec![];
+    for (address, share) in recipients {
+        let address = deps.api.addr_validate(address.as_str())
+            .map_err(|_e| ContractError::InvalidRoyaltyRecipients {  })?;
+        if share.is_zero() || !addresses.insert(address.clone()) {
+            return Err(ContractError::InvalidRoyaltyRecipients {  });
+        }
+        total_share = total_share.checked_add(share)
+            .map_err(|_e| ContractError::InvalidRoyaltyRecipients {  })?;
+        royalty_recipients.push(RoyaltyRecipient { address, share });
+    }
+
+    if total_share > MAX_TOTAL_ROYALTY {
+        return Err(ContractError::InvalidRoyaltyRecipients {  });
+    }
+
+    ROYALTY_REGISTRY.save(deps.storage, nft_contract_address.as_str(), &royalty_recipients)?;
+
+    let recipients_separated_by_comma = royalty_recipients
+        .iter()
+        .map(|royalty_recipient| format!("{}={}", royalty_recipient.address, royalty_recipient.share))
+        .collect::<Vec<String>>().join(",");
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "set_collection_royalties")
+            .add_attribute("nft_contract_address", nft_contract_address)
+            .add_attribute("recipients", recipients_separated_by_comma)
+            .add_attribute("total_share", total_share.to_string())
+    )
+}
+
+// collection falls back to the royalties of the nft contract
+pub fn remove_collection_royalties(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    if !is_collection_creator(&deps, &nft_contract_address, &info.sender) {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    if !ROYALTY_REGISTRY.has(deps.storage, nft_contract_address.as_str()) {
+        return Err(ContractError::CollectionRoyaltiesNotFound {  });
+    }
+    ROYALTY_REGISTRY.remove(deps.storage, nft_contract_address.as_str());
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "remove_collection_royalties")
+            .add_attribute("nft_contract_address", nft_contract_address)
+    )
+}
@@ -0,0 +1,191 @@
+use cosmwasm_std::{to_json_vec, Addr, Binary, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::{execute::{balance::debit_balance, collections::ensure_collection_tradable, fees::FeeRole, hooks::add_trade_hook_msgs}, msg::{SignedOrder, TradeHookAction, TradeHookMsg, SignedOrderSide, SignedOrderSignDoc}, state::{PaymentAsset, MAKER_NONCES, SIGNED_ORDERS_CANCELLED_OR_FILLED}, utils::{add_sale_payout_msgs, is_marketplace_approved, query_nft_owner, transfer_nft_msg, validate_referrer, verify_seller_can_deliver_nft}, ContractError};
+
+
+pub fn fill_signed_order(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    order: SignedOrder,
+    signature: Binary,
+    maker_pubkey: Binary,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    let referrer = validate_referrer(deps.api, referrer, &info.sender)?;
+    let maker = deps.api.addr_validate(order.maker.as_str())
+        .map_err(|_e| ContractError::InvalidSignature {  })?;
+    let nft_contract_address = deps.api.addr_validate(order.nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+    let price = order.price;
+
+    if env.block.time >= order.expires_at {
+        return Err(ContractError::SignedOrderExpired {  });
+    }
+
+    let maker_nonce = MAKER_NONCES.may_load(deps.storage, maker.as_str())?.unwrap_or_default();
+    if order.nonce < maker_nonce {
+        return Err(ContractError::InvalidNonce {  });
+    }
+
+    let order_hash = signed_order_hash(&env, &order)?;
+    if SIGNED_ORDERS_CANCELLED_OR_FILLED.has(deps.storage, order_hash.as_slice()) {
+        return Err(ContractError::SignedOrderCancelledOrFilled {  });
+    }
+
+    verify_maker_signature(&deps, &maker, &order_hash, &signature, &maker_pubkey)?;
+
+    if info.sender == maker {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    SIGNED_ORDERS_CANCELLED_OR_FILLED.save(deps.storage, order_hash.as_slice(), &Empty {})?;
+
+    let (seller, buyer, seller_fee_role, buyer_sent_amount) = match order.side {
+        SignedOrderSide::Listing => {
+            // nft stays in the maker wallet until the order is filled
+            let owner = query_nft_owner(&deps, &nft_contract_address, order.token_id.clone())?;
+            if owner != maker {
+                return Err(ContractError::NftNotOwnedByMaker {  });
+            }
+            if !is_marketplace_approved(&deps, &env, &nft_contract_address, order.token_id.clone(), owner) {
+                return Err(ContractError::NftNotApproved {  });
+            }
+
+            // taker pays the taker fee and royalties on top of the price, like when buying a listing
+            let sent_amount = info
+                .funds
+                .iter()
+                .find(|coin| coin.denom == PaymentAsset::default().id())
+                .map_or(Uint128::zero(), |coin| coin.amount);
+
+            (maker, info.sender, FeeRole::Maker, Some(sent_amount))
+        },
+        SignedOrderSide::Offer => {
+            // taker sells like into a bid, from its wallet or its escrowed listing
+            verify_seller_can_deliver_nft(&mut deps, &env, &nft_contract_address, order.token_id.clone(), &info.sender)?;
+
+            debit_balance(deps.storage, &maker, price)?;
+
+            (info.sender, maker, FeeRole::Taker, None)
+        }
+    };
+
+    // transfer nft from maker wallet or escrow to buyer
+    let transfer_nft_msg = transfer_nft_msg(&nft_contract_address, buyer.to_string(), order.token_id.clone())?;
+
+    let response = Response::new()
+        .add_message(transfer_nft_msg)
+        .add_attribute("action", "fill_signed_order")
+        .add_attribute("order_hash", order_hash.to_base64())
+        .add_attribute("price", price.to_string())
+        .add_attribute("seller", seller.to_string())
+        .add_attribute("buyer", buyer.to_string())
+        .add_attribute("nft_contract_address", nft_contract_address.clone())
+        .add_attribute("token_id", order.token_id.clone());
+
+    let response = add_sale_payout_msgs(
+        &mut deps,
+        &env,
+        &nft_contract_address,
+        order.token_id.clone(),
+        price,
+        &seller,
+        seller_fee_role,
+        &buyer,
+        &PaymentAsset::default(),
+        buyer_sent_amount,
+        referrer.as_ref(),
+        response,
+    )?;
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Sale,
+        collection: nft_contract_address.to_string(),
+        token_id: order.token_id,
+        price,
+        payment_asset: PaymentAsset::default(),
+        buyer: Some(buyer.to_string()),
+        seller: seller.to_string(),
+    }, response)?;
+
+    Ok(response)
+}
+
+pub fn cancel_signed_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    order: SignedOrder,
+) -> Result<Response, ContractError> {
+    if info.sender != order.maker {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    let order_hash = signed_order_hash(&env, &order)?;
+    if SIGNED_ORDERS_CANCELLED_OR_FILLED.has(deps.storage, order_hash.as_slice()) {
+        return Err(ContractError::SignedOrderCancelledOrFilled {  });
+    }
+
+    SIGNED_ORDERS_CANCELLED_OR_FILLED.save(deps.storage, order_hash.as_slice(), &Empty {})?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "cancel_signed_order")
+            .add_attribute("order_hash", order_hash.to_base64())
+            .add_attribute("maker", info.sender)
+            .add_attribute("nonce", order.nonce.to_string())
+    )
+}
+
+pub fn increment_nonce(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let nonce = MAKER_NONCES.may_load(deps.storage, info.sender.as_str())?.unwrap_or_default() + 1;
+    MAKER_NONCES.save(deps.storage, info.sender.as_str(), &nonce)?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "increment_nonce")
+            .add_attribute("maker", info.sender)
+            .add_attribute("nonce", nonce.to_string())
+    )
+}
+
+// sha256 of the canonical json encoding of the sign doc, binds the order to this chain and contract
+pub fn signed_order_hash(env: &Env, order: &SignedOrder) -> StdResult<Binary> {
+    let sign_doc = SignedOrderSignDoc {
+        chain_id: env.block.chain_id.clone(),
+        contract_address: env.contract.address.to_string(),
+        order: order.clone(),
+    };
+    let order_hash = Sha256::digest(to_json_vec(&sign_doc)?);
+    Ok(Binary::from(order_hash.as_slice()))
+}
+
+// signature has to be valid for the order hash and the public key has to derive the maker address
+fn verify_maker_signature(
+    deps: &DepsMut,
+    maker: &Addr,
+    order_hash: &Binary,
+    signature: &Binary,
+    maker_pubkey: &Binary,
+) -> Result<(), ContractError> {
+    let is_valid_signature = deps.api.secp256k1_verify(order_hash.as_slice(), signature.as_slice(), maker_pubkey.as_slice())
+        .map_err(|_e| ContractError::InvalidSignature {  })?;
+    if !is_valid_signature {
+        return Err(ContractError::InvalidSignature {  });
+    }
+
+    let pubkey_address = Ripemd160::digest(Sha256::digest(maker_pubkey.as_slice()));
+    let maker_canonical_address = deps.api.addr_canonicalize(maker.as_str())?;
+    if maker_canonical_address.as_slice() != pubkey_address.as_slice() {
+        return Err(ContractError::InvalidSignature {  });
+    }
+
+    Ok(())
+}
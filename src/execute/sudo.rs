@@ -0,0 +1,76 @@
+use cosmwasm_std::{DepsMut, Empty, Response};
+
+use crate::{execute::blocklist::is_address_blocked, msg::ExecuteMsg, state::{BLOCKLIST, PAUSED}, ContractError};
+
+
+pub fn pause(
+    deps: DepsMut,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", if paused { "pause" } else { "unpause" })
+    )
+}
+
+pub fn update_blocklist(
+    deps: DepsMut,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    for address in add.iter() {
+        let address = deps.api.addr_validate(address.as_str())
+            .map_err(|_e| ContractError::InvalidBlocklistAddress {  })?;
+        BLOCKLIST.save(deps.storage, address.as_str(), &Empty {})?;
+    }
+
+    for address in remove.iter() {
+        let address = deps.api.addr_validate(address.as_str())
+            .map_err(|_e| ContractError::InvalidBlocklistAddress {  })?;
+        BLOCKLIST.remove(deps.storage, address.as_str());
+    }
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "update_blocklist")
+            .add_attribute("added", add.join(","))
+            .add_attribute("removed", remove.join(","))
+    )
+}
+
+// paused or blocked senders can't execute anything
+pub fn ensure_not_paused_or_blocked(deps: &DepsMut, sender: &str) -> Result<(), ContractError> {
+    ensure_not_paused(deps)?;
+    if is_address_blocked(deps.storage, sender) {
+        return Err(ContractError::AddressBlocked {  });
+    }
+    Ok(())
+}
+
+pub fn ensure_not_paused(deps: &DepsMut) -> Result<(), ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or(false) {
+        return Err(ContractError::Paused {  });
+    }
+    Ok(())
+}
+
+// blocked senders can still cancel their orders and take their funds out, nothing else
+pub fn is_allowed_while_blocked(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::CancelListing { .. }
+            | ExecuteMsg::CancelBid { .. }
+            | ExecuteMsg::CancelAllCollectionBids { .. }
+            | ExecuteMsg::CancelCollectionBid { .. }
+            | ExecuteMsg::CancelTraitBid { .. }
+            | ExecuteMsg::CancelTokenSetBid { .. }
+            | ExecuteMsg::CancelBundle { .. }
+            | ExecuteMsg::CancelSwap { .. }
+            | ExecuteMsg::CancelSignedOrder { .. }
+            | ExecuteMsg::IncrementNonce {}
+            | ExecuteMsg::Withdraw { .. }
+            | ExecuteMsg::Claim {}
+    )
+}
@@ -0,0 +1,223 @@
+use cosmwasm_std::{Addr, DepsMut, Empty, Env, MessageInfo, Response, Storage, Uint128};
+
+use crate::{execute::{blocklist::{ensure_address_not_blocked, ensure_nft_not_blocked}, claims::add_payout, collections::ensure_collection_tradable, hooks::add_trade_hook_msgs, wash_trading::{ensure_not_wash_trade, record_sale}}, msg::{TradeHookAction, TradeHookMsg}, state::{NftItem, NftSwap, PaymentAsset, PayoutRecipientType, NFT_SWAPS, NFT_SWAPS_BY_MAKER, NFT_SWAPS_BY_TAKER, NFT_SWAPS_COUNT, NFT_SWAP_ITEMS}, utils::{ensure_nft_not_in_use, has_duplicate_nft_items, nft_items_separated_by_comma, payment_msg, transfer_nft_msg, validate_nft_items, verify_nft_in_escrow}, ContractError};
+
+
+pub fn create_swap(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    offered_nfts: Vec<(String, String)>,
+    requested_nfts: Vec<(String, String)>,
+    counterparty: Option<String>,
+) -> Result<Response, ContractError> {
+    if offered_nfts.is_empty() || offered_nfts.len() >= 100 || requested_nfts.is_empty() || requested_nfts.len() >= 100 {
+        return Err(ContractError::InvalidSwapItems {  });
+    }
+
+    let offered_nfts = validate_nft_items(deps.api, offered_nfts)?;
+    let requested_nfts = validate_nft_items(deps.api, requested_nfts)?;
+    if has_duplicate_nft_items(&offered_nfts) || has_duplicate_nft_items(&requested_nfts) {
+        return Err(ContractError::DuplicateNftItems {  });
+    }
+    for item in offered_nfts.iter().chain(requested_nfts.iter()) {
+        ensure_collection_tradable(deps.storage, &env, &item.nft_contract_address)?;
+        ensure_nft_not_blocked(deps.storage, &item.nft_contract_address, item.token_id.as_str())?;
+    }
+
+    let counterparty = counterparty
+        .map(|counterparty| deps.api.addr_validate(counterparty.as_str()))
+        .transpose()
+        .map_err(|_e| ContractError::InvalidCounterparty {  })?;
+    if counterparty.as_ref() == Some(&info.sender) {
+        return Err(ContractError::InvalidCounterparty {  });
+    }
+
+    let offered_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == PaymentAsset::default().id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    let id = NFT_SWAPS_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    NFT_SWAPS_COUNT.save(deps.storage, &id)?;
+
+    for item in offered_nfts.iter() {
+        ensure_nft_not_in_use(deps.storage, item)?;
+        NFT_SWAP_ITEMS.save(deps.storage, (item.nft_contract_address.as_str(), item.token_id.as_str()), &id)?;
+
+        // verify that escrow has the NFT
+        verify_nft_in_escrow(&deps, &env, &item.nft_contract_address, item.token_id.clone())?;
+    }
+
+    let nft_swap = NftSwap {
+        id,
+        maker: info.sender,
+        offered_nfts,
+        offered_amount,
+        requested_nfts,
+        counterparty,
+    };
+
+    NFT_SWAPS.save(deps.storage, id, &nft_swap)
+        .map_err(|_e| ContractError::ErrorCreatingNewSwap {  })?;
+    NFT_SWAPS_BY_MAKER.save(deps.storage, (nft_swap.maker.as_str(), id), &Empty {})?;
+    if let Some(counterparty) = &nft_swap.counterparty {
+        NFT_SWAPS_BY_TAKER.save(deps.storage, (counterparty.as_str(), id), &Empty {})?;
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "create_swap")
+        .add_attribute("swap_id", id.to_string())
+        .add_attribute("maker", nft_swap.maker.clone())
+        .add_attribute("counterparty", nft_swap.counterparty.as_ref().map_or(String::new(), |counterparty| counterparty.to_string()))
+        .add_attribute("offered_nfts", nft_items_separated_by_comma(&nft_swap.offered_nfts))
+        .add_attribute("offered_amount", nft_swap.offered_amount.to_string())
+        .add_attribute("requested_nfts", nft_items_separated_by_comma(&nft_swap.requested_nfts));
+
+    add_swap_hook_msgs(deps.storage, TradeHookAction::Listing, &nft_swap.offered_nfts, &nft_swap.maker, None, response)
+}
+
+pub fn accept_swap(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    swap_id: u64,
+) -> Result<Response, ContractError> {
+    let nft_swap = NFT_SWAPS.load(deps.storage, swap_id)
+        .map_err(|_e| ContractError::NftSwapNotFound {  })?;
+
+    if let Some(counterparty) = &nft_swap.counterparty {
+        if info.sender != counterparty {
+            return Err(ContractError::Unauthorized {  });
+        }
+    }
+
+    remove_swap(deps.storage, &nft_swap);
+
+    ensure_address_not_blocked(deps.storage, &nft_swap.maker)?;
+    if info.sender == nft_swap.maker {
+        return Err(ContractError::SelfTrade {  });
+    }
+    // every nft changes hands like in a sale, offered ones from maker to taker and requested ones the other way
+    for item in nft_swap.offered_nfts.iter() {
+        ensure_collection_tradable(deps.storage, &env, &item.nft_contract_address)?;
+        ensure_nft_not_blocked(deps.storage, &item.nft_contract_address, item.token_id.as_str())?;
+        ensure_not_wash_trade(deps.storage, &env, &item.nft_contract_address, item.token_id.as_str(), &nft_swap.maker, &info.sender)?;
+        record_sale(deps.storage, &env, &item.nft_contract_address, item.token_id.as_str(), &nft_swap.maker, &info.sender)?;
+    }
+
+    // verify that taker escrowed every requested nft and none of them belongs to another order
+    for item in nft_swap.requested_nfts.iter() {
+        ensure_collection_tradable(deps.storage, &env, &item.nft_contract_address)?;
+        ensure_nft_not_blocked(deps.storage, &item.nft_contract_address, item.token_id.as_str())?;
+        ensure_nft_not_in_use(deps.storage, item)?;
+        verify_nft_in_escrow(&deps, &env, &item.nft_contract_address, item.token_id.clone())?;
+        ensure_not_wash_trade(deps.storage, &env, &item.nft_contract_address, item.token_id.as_str(), &info.sender, &nft_swap.maker)?;
+        record_sale(deps.storage, &env, &item.nft_contract_address, item.token_id.as_str(), &info.sender, &nft_swap.maker)?;
+    }
+
+    // transfer requested nfts from escrow to maker
+    let transfer_requested_nft_msgs = nft_swap.requested_nfts
+        .iter()
+        .map(|item| transfer_nft_msg(&item.nft_contract_address, nft_swap.maker.to_string(), item.token_id.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // transfer offered nfts from escrow to taker
+    let transfer_offered_nft_msgs = nft_swap.offered_nfts
+        .iter()
+        .map(|item| transfer_nft_msg(&item.nft_contract_address, info.sender.to_string(), item.token_id.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut response = Response::new()
+        .add_messages(transfer_requested_nft_msgs)
+        .add_messages(transfer_offered_nft_msgs)
+        .add_attribute("action", "accept_swap")
+        .add_attribute("swap_id", swap_id.to_string())
+        .add_attribute("maker", nft_swap.maker.clone())
+        .add_attribute("taker", info.sender.to_string())
+        .add_attribute("offered_nfts", nft_items_separated_by_comma(&nft_swap.offered_nfts))
+        .add_attribute("offered_amount", nft_swap.offered_amount.to_string())
+        .add_attribute("requested_nfts", nft_items_separated_by_comma(&nft_swap.requested_nfts));
+
+    // offered sei pays the taker for the requested nfts like a seller, pushed or credited to its claimable balance
+    response = add_payout(deps.storage, PayoutRecipientType::Seller, &PaymentAsset::default(), info.sender.to_string(), nft_swap.offered_amount, response)?;
+
+    let response = add_swap_hook_msgs(deps.storage, TradeHookAction::Sale, &nft_swap.offered_nfts, &nft_swap.maker, Some(&info.sender), response)?;
+    add_swap_hook_msgs(deps.storage, TradeHookAction::Sale, &nft_swap.requested_nfts, &info.sender, Some(&nft_swap.maker), response)
+}
+
+pub fn cancel_swap(
+    deps: DepsMut,
+    info: MessageInfo,
+    swap_id: u64,
+) -> Result<Response, ContractError> {
+    let nft_swap = NFT_SWAPS.load(deps.storage, swap_id)
+        .map_err(|_e| ContractError::NftSwapNotFound {  })?;
+
+    if info.sender != nft_swap.maker {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    // blocked items stay in escrow until resolved
+    for item in nft_swap.offered_nfts.iter() {
+        ensure_nft_not_blocked(deps.storage, &item.nft_contract_address, item.token_id.as_str())?;
+    }
+
+    remove_swap(deps.storage, &nft_swap);
+
+    // transfer offered nfts from escrow back to maker
+    let transfer_nft_msgs = nft_swap.offered_nfts
+        .iter()
+        .map(|item| transfer_nft_msg(&item.nft_contract_address, nft_swap.maker.to_string(), item.token_id.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut response = Response::new()
+        .add_messages(transfer_nft_msgs)
+        .add_attribute("action", "cancel_swap")
+        .add_attribute("swap_id", swap_id.to_string())
+        .add_attribute("maker", nft_swap.maker.clone())
+        .add_attribute("offered_nfts", nft_items_separated_by_comma(&nft_swap.offered_nfts))
+        .add_attribute("offered_amount", nft_swap.offered_amount.to_string());
+
+    // transfer offered sei from escrow back to maker
+    if !nft_swap.offered_amount.is_zero() {
+        let transfer_sei_msg = payment_msg(&PaymentAsset::default(), nft_swap.maker.to_string(), nft_swap.offered_amount)?;
+        response = response.add_message(transfer_sei_msg);
+    }
+
+    add_swap_hook_msgs(deps.storage, TradeHookAction::Cancel, &nft_swap.offered_nfts, &nft_swap.maker, None, response)
+}
+
+pub fn remove_swap(storage: &mut dyn Storage, nft_swap: &NftSwap) {
+    for item in nft_swap.offered_nfts.iter() {
+        NFT_SWAP_ITEMS.remove(storage, (item.nft_contract_address.as_str(), item.token_id.as_str()));
+    }
+    NFT_SWAPS_BY_MAKER.remove(storage, (nft_swap.maker.as_str(), nft_swap.id));
+    if let Some(counterparty) = &nft_swap.counterparty {
+        NFT_SWAPS_BY_TAKER.remove(storage, (counterparty.as_str(), nft_swap.id));
+    }
+    NFT_SWAPS.remove(storage, nft_swap.id);
+}
+
+// nfts are traded against each other, so every nft is reported with price 0
+fn add_swap_hook_msgs(
+    storage: &dyn Storage,
+    action: TradeHookAction,
+    items: &[NftItem],
+    seller: &Addr,
+    buyer: Option<&Addr>,
+    response: Response,
+) -> Result<Response, ContractError> {
+    items.iter().try_fold(response, |response, item| {
+        add_trade_hook_msgs(storage, TradeHookMsg {
+            action: action.clone(),
+            collection: item.nft_contract_address.to_string(),
+            token_id: item.token_id.clone(),
+            price: Uint128::zero(),
+            payment_asset: PaymentAsset::default(),
+            buyer: buyer.map(|buyer| buyer.to_string()),
+            seller: seller.to_string(),
+        }, response)
+    })
+}
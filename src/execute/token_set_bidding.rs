@@ -0,0 +1,165 @@
+use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, Uint128};
+
+use crate::{execute::{collections::ensure_collection_tradable, fees::FeeRole, hooks::add_trade_hook_msgs}, msg::{TradeHookAction, TradeHookMsg}, state::{NftTokenSetBid, PaymentAsset, NFT_TOKEN_SET_BIDS, NFT_TOKEN_SET_BIDS_COUNT}, utils::{add_sale_payout_msgs, payment_msg, transfer_nft_msg, validate_referrer, verify_merkle_proof, verify_seller_can_deliver_nft}, ContractError};
+
+
+pub fn token_set_bid(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    price: Uint128,
+    nft_contract_address: String,
+    merkle_root: Binary,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+
+    if merkle_root.len() != 32 {
+        return Err(ContractError::InvalidMerkleRoot {  });
+    }
+
+    let sent_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == PaymentAsset::default().id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    // Check if the sent amount is sufficient
+    if sent_amount < price {
+        return Err(ContractError::InsufficientFundsSent {  });
+    }
+
+    let id = NFT_TOKEN_SET_BIDS_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    NFT_TOKEN_SET_BIDS_COUNT.save(deps.storage, &id)?;
+
+    let nft_token_set_bid = NftTokenSetBid {
+        id,
+        bidder: info.sender,
+        price,
+        nft_contract_address,
+        merkle_root,
+    };
+
+    NFT_TOKEN_SET_BIDS.save(
+        deps.storage,
+        (nft_token_set_bid.nft_contract_address.as_str(), id),
+        &nft_token_set_bid
+    )
+        .map_err(|_e| ContractError::ErrorCreatingNewTokenSetBid {  })?;
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "token_set_bid")
+            .add_attribute("token_set_bid_id", id.to_string())
+            .add_attribute("price", nft_token_set_bid.price.to_string())
+            .add_attribute("bidder", nft_token_set_bid.bidder)
+            .add_attribute("nft_contract_address", nft_token_set_bid.nft_contract_address)
+            .add_attribute("merkle_root", nft_token_set_bid.merkle_root.to_base64())
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn sell_to_token_set_bid(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    nft_contract_address: String,
+    token_id: String,
+    token_set_bid_id: u64,
+    proof: Vec<Binary>,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    let referrer = validate_referrer(deps.api, referrer, &info.sender)?;
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+
+    let key = (nft_contract_address.as_str(), token_set_bid_id);
+
+    let nft_token_set_bid = NFT_TOKEN_SET_BIDS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftTokenSetBidNotFound {  })?;
+
+    NFT_TOKEN_SET_BIDS.remove(deps.storage, key);
+
+    // verify that token is in the set the bidder committed to
+    if !verify_merkle_proof(&nft_token_set_bid.merkle_root, token_id.as_str(), &proof) {
+        return Err(ContractError::InvalidMerkleProof {  });
+    }
+
+    verify_seller_can_deliver_nft(&mut deps, &env, &nft_contract_address, token_id.clone(), &info.sender)?;
+
+    // transfer nft from escrow or seller wallet to bidder
+    let transfer_nft_msg = transfer_nft_msg(&nft_contract_address, nft_token_set_bid.bidder.to_string(), token_id.clone())?;
+
+    let response = Response::new()
+        .add_message(transfer_nft_msg)
+        .add_attribute("action", "sell_to_token_set_bid")
+        .add_attribute("token_set_bid_id", token_set_bid_id.to_string())
+        .add_attribute("price", nft_token_set_bid.price.to_string())
+        .add_attribute("bidder", nft_token_set_bid.bidder.clone())
+        .add_attribute("seller", info.sender.to_string())
+        .add_attribute("nft_contract_address", nft_token_set_bid.nft_contract_address.clone())
+        .add_attribute("token_id", token_id.clone());
+
+    let response = add_sale_payout_msgs(
+        &mut deps,
+        &env,
+        &nft_token_set_bid.nft_contract_address,
+        token_id.clone(),
+        nft_token_set_bid.price,
+        &info.sender,
+        FeeRole::Taker,
+        &nft_token_set_bid.bidder,
+        &PaymentAsset::default(),
+        None,
+        referrer.as_ref(),
+        response,
+    )?;
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Sale,
+        collection: nft_token_set_bid.nft_contract_address.to_string(),
+        token_id,
+        price: nft_token_set_bid.price,
+        payment_asset: PaymentAsset::default(),
+        buyer: Some(nft_token_set_bid.bidder.to_string()),
+        seller: info.sender.to_string(),
+    }, response)?;
+
+    Ok(response)
+}
+
+pub fn cancel_token_set_bid(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+    token_set_bid_id: u64,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let key = (nft_contract_address.as_str(), token_set_bid_id);
+
+    let nft_token_set_bid = NFT_TOKEN_SET_BIDS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftTokenSetBidNotFound {  })?;
+
+    if info.sender != nft_token_set_bid.bidder {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    NFT_TOKEN_SET_BIDS.remove(deps.storage, key);
+
+    // transfer sei from escrow back to bidder
+    let transfer_sei_msg = payment_msg(&PaymentAsset::default(), nft_token_set_bid.bidder.to_string(), nft_token_set_bid.price)?;
+
+    Ok(
+        Response::new()
+            .add_message(transfer_sei_msg)
+            .add_attribute("action", "cancel_token_set_bid")
+            .add_attribute("token_set_bid_id", token_set_bid_id.to_string())
+            .add_attribute("price", nft_token_set_bid.price.to_string())
+            .add_attribute("bidder", nft_token_set_bid.bidder)
+            .add_attribute("nft_contract_address", nft_token_set_bid.nft_contract_address)
+    )
+}
@@ -0,0 +1,169 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+
+use crate::{execute::{collections::ensure_collection_tradable, fees::FeeRole, hooks::add_trade_hook_msgs}, msg::{TradeHookAction, TradeHookMsg}, state::{NftTraitBid, PaymentAsset, TraitFilter, NFT_TRAIT_BIDS, NFT_TRAIT_BIDS_COUNT}, utils::{add_sale_payout_msgs, metadata_matches_traits, payment_msg, query_nft_metadata, transfer_nft_msg, validate_referrer, verify_seller_can_deliver_nft}, ContractError};
+
+
+pub fn trait_bid(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    price: Uint128,
+    nft_contract_address: String,
+    traits: Vec<TraitFilter>,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+
+    if traits.is_empty() || traits.iter().any(|trait_filter| trait_filter.trait_type.is_empty()) {
+        return Err(ContractError::InvalidTraits {  });
+    }
+
+    let sent_amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == PaymentAsset::default().id())
+        .map_or(Uint128::zero(), |coin| coin.amount);
+
+    // Check if the sent amount is sufficient
+    if sent_amount < price {
+        return Err(ContractError::InsufficientFundsSent {  });
+    }
+
+    let id = NFT_TRAIT_BIDS_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    NFT_TRAIT_BIDS_COUNT.save(deps.storage, &id)?;
+
+    let nft_trait_bid = NftTraitBid {
+        id,
+        bidder: info.sender,
+        price,
+        nft_contract_address,
+        traits,
+    };
+
+    NFT_TRAIT_BIDS.save(
+        deps.storage,
+        (nft_trait_bid.nft_contract_address.as_str(), id),
+        &nft_trait_bid
+    )
+        .map_err(|_e| ContractError::ErrorCreatingNewTraitBid {  })?;
+
+    let traits_separated_by_comma = nft_trait_bid.traits
+        .iter()
+        .map(|trait_filter| format!("{}={}", trait_filter.trait_type, trait_filter.value))
+        .collect::<Vec<String>>().join(",");
+
+    Ok(
+        Response::new()
+            .add_attribute("action", "trait_bid")
+            .add_attribute("trait_bid_id", id.to_string())
+            .add_attribute("price", nft_trait_bid.price.to_string())
+            .add_attribute("bidder", nft_trait_bid.bidder)
+            .add_attribute("nft_contract_address", nft_trait_bid.nft_contract_address)
+            .add_attribute("traits", traits_separated_by_comma)
+    )
+}
+
+pub fn sell_to_trait_bid(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    nft_contract_address: String,
+    token_id: String,
+    trait_bid_id: u64,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    let referrer = validate_referrer(deps.api, referrer, &info.sender)?;
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+    ensure_collection_tradable(deps.storage, &env, &nft_contract_address)?;
+
+    let key = (nft_contract_address.as_str(), trait_bid_id);
+
+    let nft_trait_bid = NFT_TRAIT_BIDS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftTraitBidNotFound {  })?;
+
+    NFT_TRAIT_BIDS.remove(deps.storage, key);
+
+    verify_seller_can_deliver_nft(&mut deps, &env, &nft_contract_address, token_id.clone(), &info.sender)?;
+
+    // verify that nft has all the traits the bidder asked for
+    let metadata = query_nft_metadata(&deps, nft_contract_address.to_string(), token_id.clone())?;
+    if !metadata_matches_traits(&metadata, &nft_trait_bid.traits) {
+        return Err(ContractError::NftTraitsMismatch {  });
+    }
+
+    // transfer nft from escrow or seller wallet to bidder
+    let transfer_nft_msg = transfer_nft_msg(&nft_contract_address, nft_trait_bid.bidder.to_string(), token_id.clone())?;
+
+    let response = Response::new()
+        .add_message(transfer_nft_msg)
+        .add_attribute("action", "sell_to_trait_bid")
+        .add_attribute("trait_bid_id", trait_bid_id.to_string())
+        .add_attribute("price", nft_trait_bid.price.to_string())
+        .add_attribute("bidder", nft_trait_bid.bidder.clone())
+        .add_attribute("seller", info.sender.to_string())
+        .add_attribute("nft_contract_address", nft_trait_bid.nft_contract_address.clone())
+        .add_attribute("token_id", token_id.clone());
+
+    let response = add_sale_payout_msgs(
+        &mut deps,
+        &env,
+        &nft_trait_bid.nft_contract_address,
+        token_id.clone(),
+        nft_trait_bid.price,
+        &info.sender,
+        FeeRole::Taker,
+        &nft_trait_bid.bidder,
+        &PaymentAsset::default(),
+        None,
+        referrer.as_ref(),
+        response,
+    )?;
+
+    let response = add_trade_hook_msgs(deps.storage, TradeHookMsg {
+        action: TradeHookAction::Sale,
+        collection: nft_trait_bid.nft_contract_address.to_string(),
+        token_id,
+        price: nft_trait_bid.price,
+        payment_asset: PaymentAsset::default(),
+        buyer: Some(nft_trait_bid.bidder.to_string()),
+        seller: info.sender.to_string(),
+    }, response)?;
+
+    Ok(response)
+}
+
+pub fn cancel_trait_bid(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_contract_address: String,
+    trait_bid_id: u64,
+) -> Result<Response, ContractError> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+
+    let key = (nft_contract_address.as_str(), trait_bid_id);
+
+    let nft_trait_bid = NFT_TRAIT_BIDS.load(deps.storage, key)
+        .map_err(|_e| ContractError::NftTraitBidNotFound {  })?;
+
+    if info.sender != nft_trait_bid.bidder {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    NFT_TRAIT_BIDS.remove(deps.storage, key);
+
+    // transfer sei from escrow back to bidder
+    let transfer_sei_msg = payment_msg(&PaymentAsset::default(), nft_trait_bid.bidder.to_string(), nft_trait_bid.price)?;
+
+    Ok(
+        Response::new()
+            .add_message(transfer_sei_msg)
+            .add_attribute("action", "cancel_trait_bid")
+            .add_attribute("trait_bid_id", trait_bid_id.to_string())
+            .add_attribute("price", nft_trait_bid.price.to_string())
+            .add_attribute("bidder", nft_trait_bid.bidder)
+            .add_attribute("nft_contract_address", nft_trait_bid.nft_contract_address)
+    )
+}
@@ -0,0 +1,46 @@
+use cosmwasm_std::{Addr, Env, StdResult, Storage};
+
+use crate::{state::{LastSale, CONFIG, LAST_SALES}, ContractError};
+
+
+// rejects self trades and, while the resale cooldown runs, selling an nft straight back to the address it was bought from
+pub fn ensure_not_wash_trade(
+    storage: &dyn Storage,
+    env: &Env,
+    nft_contract_address: &Addr,
+    token_id: &str,
+    seller: &Addr,
+    buyer: &Addr,
+) -> Result<(), ContractError> {
+    if seller == buyer {
+        return Err(ContractError::SelfTrade {  });
+    }
+
+    let resale_cooldown_seconds = CONFIG.may_load(storage)?.map_or(0, |config| config.resale_cooldown_seconds);
+    if resale_cooldown_seconds == 0 {
+        return Ok(());
+    }
+
+    if let Some(last_sale) = LAST_SALES.may_load(storage, (nft_contract_address.as_str(), token_id))? {
+        let is_sold_back = last_sale.buyer == *seller && last_sale.seller == *buyer;
+        if is_sold_back && env.block.time < last_sale.sold_at.plus_seconds(resale_cooldown_seconds) {
+            return Err(ContractError::ResaleCooldownActive {  });
+        }
+    }
+    Ok(())
+}
+
+pub fn record_sale(
+    storage: &mut dyn Storage,
+    env: &Env,
+    nft_contract_address: &Addr,
+    token_id: &str,
+    seller: &Addr,
+    buyer: &Addr,
+) -> StdResult<()> {
+    LAST_SALES.save(storage, (nft_contract_address.as_str(), token_id), &LastSale {
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        sold_at: env.block.time,
+    })
+}
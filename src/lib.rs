@@ -5,5 +5,6 @@ pub mod state;
 pub mod query;
 pub mod execute;
 pub mod utils;
+pub mod migration;
 
 pub use crate::error::ContractError;
@@ -0,0 +1,400 @@
+use cosmwasm_std::{Addr, Binary, Decimal, DepsMut, Empty, Event, Order, Response, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Map};
+use serde::{Deserialize, Serialize};
+
+use crate::{execute::collection_bidding::{index_collection_bid_prices, unindex_collection_bid_prices}, state::{BidFunding, BundleRoyaltyRule, ListingCustody, ListingReservation, MigrationProgress, NftBid, NftBundle, NftCollectionBid, NftItem, NftListing, NftTokenSetBid, NftTraitBid, PaymentAsset, TraitFilter, MIGRATION_PROGRESS, NFT_BIDS, NFT_BUNDLES, NFT_COLLECTION_BIDS, NFT_LISTINGS, NFT_TOKEN_SET_BIDS, NFT_TRAIT_BIDS}, ContractError};
+
+pub const DEFAULT_MIGRATION_LIMIT: u32 = 200;
+
+// migrates up to limit entries of the namespace after start_after and returns how many it went through
+// together with the key to continue after, or None once the step is done
+type MigrationStep = fn(&mut dyn Storage, &str, Option<Vec<u8>>, u32, &mut Vec<Event>) -> Result<(u32, Option<Vec<u8>>), ContractError>;
+
+// ordered by version, a step runs when migrating from a version below the one it was introduced in
+// steps have to be idempotent, converted entries are recorded in MIGRATED_KEYS under the namespace of the step
+// and skipped when the step runs over them again, the keys are removed once the step is done
+const MIGRATION_STEPS: &[(&str, &str, MigrationStep)] = &[
+    ("0.2.0", "nft_listings", migrate_listing_prices),
+    ("0.2.0", "nft_bids", migrate_bid_prices),
+    ("0.2.0", "nft_collection_bids", migrate_collection_bid_prices),
+    ("0.2.0", "nft_trait_bids", migrate_trait_bid_prices),
+    ("0.2.0", "nft_token_set_bids", migrate_token_set_bid_prices),
+    ("0.2.0", "nft_bundles", migrate_bundle_prices),
+];
+
+// major.minor.patch, pre-release and build metadata are ignored
+pub fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let version = version.split(['-', '+']).next().unwrap_or_default();
+    let parts = version
+        .split('.')
+        .map(|part| part.parse::<u64>().map_err(|_e| ContractError::InvalidContractVersion {  }))
+        .collect::<Result<Vec<u64>, ContractError>>()?;
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(ContractError::InvalidContractVersion {  }),
+    }
+}
+
+// runs the steps pending for the progress until limit entries were migrated
+// returns the progress to continue from, or None once every step is done
+pub fn run_migration_steps(
+    storage: &mut dyn Storage,
+    mut progress: MigrationProgress,
+    limit: u32,
+    events: &mut Vec<Event>,
+) -> Result<Option<MigrationProgress>, ContractError> {
+    let from_version = parse_version(progress.from_version.as_str())?;
+    let mut remaining = limit;
+
+    while let Some((version, namespace, step)) = MIGRATION_STEPS.get(progress.step as usize) {
+        if parse_version(version)? <= from_version {
+            progress.step += 1;
+            continue;
+        }
+        if remaining == 0 {
+            return Ok(Some(progress));
+        }
+
+        // the step is done, its recorded keys are removed against the same limit
+        if progress.clearing_migrated_keys {
+            let (cleared, done) = clear_migrated_keys(storage, namespace, remaining)?;
+            remaining -= cleared;
+            if done {
+                progress.clearing_migrated_keys = false;
+                progress.step += 1;
+            }
+            continue;
+        }
+
+        let start_after = progress.start_after.take().map(|start_after| start_after.to_vec());
+        let (migrated, next) = step(storage, namespace, start_after, remaining, events)?;
+        remaining -= migrated;
+        progress.migrated += u64::from(migrated);
+        match next {
+            Some(next) => progress.start_after = Some(Binary::from(next)),
+            None => progress.clearing_migrated_keys = true,
+        }
+    }
+
+    Ok(None)
+}
+
+// saves the progress when steps are left, trading is blocked until it is removed
+pub fn save_migration_progress(storage: &mut dyn Storage, progress: &Option<MigrationProgress>) -> StdResult<()> {
+    match progress {
+        Some(progress) => MIGRATION_PROGRESS.save(storage, progress),
+        None => {
+            MIGRATION_PROGRESS.remove(storage);
+            Ok(())
+        },
+    }
+}
+
+// anyone can push a paginated migration forward
+pub fn continue_migration(
+    deps: DepsMut,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let progress = MIGRATION_PROGRESS.may_load(deps.storage)?
+        .ok_or(ContractError::NoMigrationInProgress {  })?;
+    let mut events = vec![];
+    let progress = run_migration_steps(deps.storage, progress, limit.unwrap_or(DEFAULT_MIGRATION_LIMIT), &mut events)?;
+    save_migration_progress(deps.storage, &progress)?;
+
+    let mut response = Response::new()
+        .add_events(events)
+        .add_attribute("action", "continue_migration")
+        .add_attribute("migration_finished", progress.is_none().to_string());
+    if let Some(progress) = progress {
+        response = response.add_attribute("migrated", progress.migrated.to_string());
+    }
+
+    Ok(response)
+}
+
+fn range_after<'a, T: Serialize + serde::de::DeserializeOwned, K: cw_storage_plus::PrimaryKey<'a>>(
+    storage: &dyn Storage,
+    map: &Map<'a, K, T>,
+    start_after: Option<Vec<u8>>,
+    limit: u32,
+) -> StdResult<Vec<(Vec<u8>, T)>> {
+    map
+        .range_raw(storage, start_after.map(Bound::ExclusiveRaw), None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+// the key of the last entry when the page was full, more entries may follow it
+fn next_start_after<T>(entries: &[(Vec<u8>, T)], limit: u32) -> Option<Vec<u8>> {
+    if entries.len() < limit as usize {
+        return None;
+    }
+    entries.last().map(|(key, _)| key.clone())
+}
+
+// key: (namespace of the migrated map, raw key of the entry), entries already converted by the step of the namespace
+// a rerun over them, e.g. of a page that was only partly saved as progress, would convert the prices twice,
+// only kept until the step is done
+const MIGRATED_KEYS: Map<(&str, &[u8]), Empty> = Map::new("migrated_keys");
+
+// leaves the entries not converted yet and records them as converted
+fn take_unmigrated<T>(storage: &mut dyn Storage, namespace: &str, entries: Vec<(Vec<u8>, T)>) -> StdResult<Vec<T>> {
+    let mut unmigrated = vec![];
+    for (key, entry) in entries {
+        if MIGRATED_KEYS.has(storage, (namespace, key.as_slice())) {
+            continue;
+        }
+        MIGRATED_KEYS.save(storage, (namespace, key.as_slice()), &Empty {})?;
+        unmigrated.push(entry);
+    }
+    Ok(unmigrated)
+}
+
+// removes up to limit recorded keys of the namespace, returns how many it removed and whether none are left
+fn clear_migrated_keys(storage: &mut dyn Storage, namespace: &str, limit: u32) -> StdResult<(u32, bool)> {
+    let keys = MIGRATED_KEYS
+        .prefix(namespace)
+        .keys_raw(storage, None, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<Vec<Vec<u8>>>();
+    for key in keys.iter() {
+        MIGRATED_KEYS.remove(storage, (namespace, key.as_slice()));
+    }
+    Ok((keys.len() as u32, keys.len() < limit as usize))
+}
+
+// up to 0.1.0 prices were stored as Decimal whole tokens, the same namespaces are read with the old layout here
+
+// how fractions of a usei of a legacy price are dropped
+#[derive(Clone, Copy)]
+enum LegacyPriceRounding {
+    // prices backed by escrowed funds, 0.1.0 only escrowed the truncated price so more can't be paid out
+    Down,
+    // asks of listings and bundles, nothing is escrowed for them
+    HalfUp,
+}
+
+// 1 sei = 1_000_000 usei and Decimal has 18 fractional digits, so whole tokens are atomics / 10^12
+// every price with a fraction of a usei is reported in a legacy_price_rounded event
+fn legacy_price_to_usei(price: Decimal, rounding: LegacyPriceRounding, entry: &str, events: &mut Vec<Event>) -> Result<Uint128, ContractError> {
+    let usei_atomics = Uint128::new(1_000_000_000_000);
+    let atomics = match rounding {
+        LegacyPriceRounding::Down => price.atomics(),
+        LegacyPriceRounding::HalfUp => price
+            .atomics()
+            .checked_add(Uint128::new(500_000_000_000))
+            .map_err(|_e| ContractError::ParseDecimal {  })?,
+    };
+    let usei = atomics / usei_atomics;
+
+    if !(price.atomics() % usei_atomics).is_zero() {
+        events.push(
+            Event::new("legacy_price_rounded")
+                .add_attribute("entry", entry)
+                .add_attribute("legacy_price", price.to_string())
+                .add_attribute("price", usei.to_string())
+        );
+    }
+
+    Ok(usei)
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyNftListing {
+    lister: Addr,
+    price: Decimal,
+    nft_contract_address: Addr,
+    token_id: String,
+    reservation: Option<ListingReservation>,
+    #[serde(default)]
+    custody: ListingCustody,
+    #[serde(default)]
+    payment_asset: PaymentAsset,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyNftBid {
+    bidder: Addr,
+    price: Decimal,
+    nft_contract_address: Addr,
+    token_id: String,
+    #[serde(default)]
+    funding: BidFunding,
+    #[serde(default)]
+    payment_asset: PaymentAsset,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyNftCollectionBid {
+    bidder: Addr,
+    nft_contract_address: Addr,
+    bids_prices: Vec<Decimal>,
+    #[serde(default)]
+    funding: BidFunding,
+    #[serde(default)]
+    payment_asset: PaymentAsset,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyNftTraitBid {
+    id: u64,
+    bidder: Addr,
+    price: Decimal,
+    nft_contract_address: Addr,
+    traits: Vec<TraitFilter>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyNftTokenSetBid {
+    id: u64,
+    bidder: Addr,
+    price: Decimal,
+    nft_contract_address: Addr,
+    merkle_root: Binary,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyNftBundle {
+    id: u64,
+    lister: Addr,
+    price: Decimal,
+    items: Vec<NftItem>,
+    royalty_rule: BundleRoyaltyRule,
+}
+
+const LEGACY_NFT_LISTINGS: Map<(&str, &str), LegacyNftListing> = Map::new("nft_listings");
+const LEGACY_NFT_BIDS: Map<(&str, &str, &str), LegacyNftBid> = Map::new("nft_bids");
+const LEGACY_NFT_COLLECTION_BIDS: Map<(&str, &str), LegacyNftCollectionBid> = Map::new("nft_collection_bids");
+const LEGACY_NFT_TRAIT_BIDS: Map<(&str, u64), LegacyNftTraitBid> = Map::new("nft_trait_bids");
+const LEGACY_NFT_TOKEN_SET_BIDS: Map<(&str, u64), LegacyNftTokenSetBid> = Map::new("nft_token_set_bids");
+const LEGACY_NFT_BUNDLES: Map<u64, LegacyNftBundle> = Map::new("nft_bundles");
+
+fn migrate_listing_prices(storage: &mut dyn Storage, namespace: &str, start_after: Option<Vec<u8>>, limit: u32, events: &mut Vec<Event>) -> Result<(u32, Option<Vec<u8>>), ContractError> {
+    let legacy_listings = range_after(storage, &LEGACY_NFT_LISTINGS, start_after, limit)?;
+    let next = next_start_after(&legacy_listings, limit);
+    let migrated = legacy_listings.len() as u32;
+
+    for legacy_listing in take_unmigrated(storage, namespace, legacy_listings)? {
+        let entry = format!("listing:{}:{}", legacy_listing.nft_contract_address, legacy_listing.token_id);
+        let nft_listing = NftListing {
+            lister: legacy_listing.lister,
+            price: legacy_price_to_usei(legacy_listing.price, LegacyPriceRounding::HalfUp, entry.as_str(), events)?,
+            nft_contract_address: legacy_listing.nft_contract_address,
+            token_id: legacy_listing.token_id,
+            reservation: legacy_listing.reservation,
+            custody: legacy_listing.custody,
+            payment_asset: legacy_listing.payment_asset,
+        };
+        NFT_LISTINGS.save(storage, (nft_listing.nft_contract_address.as_str(), nft_listing.token_id.as_str()), &nft_listing)?;
+    }
+
+    Ok((migrated, next))
+}
+
+fn migrate_bid_prices(storage: &mut dyn Storage, namespace: &str, start_after: Option<Vec<u8>>, limit: u32, events: &mut Vec<Event>) -> Result<(u32, Option<Vec<u8>>), ContractError> {
+    let legacy_bids = range_after(storage, &LEGACY_NFT_BIDS, start_after, limit)?;
+    let next = next_start_after(&legacy_bids, limit);
+    let migrated = legacy_bids.len() as u32;
+
+    for legacy_bid in take_unmigrated(storage, namespace, legacy_bids)? {
+        let entry = format!("bid:{}:{}:{}", legacy_bid.nft_contract_address, legacy_bid.token_id, legacy_bid.bidder);
+        let nft_bid = NftBid {
+            bidder: legacy_bid.bidder,
+            price: legacy_price_to_usei(legacy_bid.price, LegacyPriceRounding::Down, entry.as_str(), events)?,
+            nft_contract_address: legacy_bid.nft_contract_address,
+            token_id: legacy_bid.token_id,
+            funding: legacy_bid.funding,
+            payment_asset: legacy_bid.payment_asset,
+        };
+        NFT_BIDS.save(storage, (nft_bid.nft_contract_address.as_str(), nft_bid.token_id.as_str(), nft_bid.bidder.as_str()), &nft_bid)?;
+    }
+
+    Ok((migrated, next))
+}
+
+fn migrate_collection_bid_prices(storage: &mut dyn Storage, namespace: &str, start_after: Option<Vec<u8>>, limit: u32, events: &mut Vec<Event>) -> Result<(u32, Option<Vec<u8>>), ContractError> {
+    let legacy_collection_bids = range_after(storage, &LEGACY_NFT_COLLECTION_BIDS, start_after, limit)?;
+    let next = next_start_after(&legacy_collection_bids, limit);
+    let migrated = legacy_collection_bids.len() as u32;
+
+    for legacy_collection_bid in take_unmigrated(storage, namespace, legacy_collection_bids)? {
+        let entry = format!("collection_bid:{}:{}", legacy_collection_bid.nft_contract_address, legacy_collection_bid.bidder);
+        let legacy_atomics = legacy_collection_bid.bids_prices.iter().map(|price| price.atomics()).collect::<Vec<_>>();
+        let nft_collection_bid = NftCollectionBid {
+            bidder: legacy_collection_bid.bidder,
+            nft_contract_address: legacy_collection_bid.nft_contract_address,
+            bids_prices: legacy_collection_bid.bids_prices
+                .into_iter()
+                .map(|price| legacy_price_to_usei(price, LegacyPriceRounding::Down, entry.as_str(), events))
+                .collect::<Result<Vec<_>, ContractError>>()?,
+            funding: legacy_collection_bid.funding,
+            payment_asset: legacy_collection_bid.payment_asset,
+        };
+        NFT_COLLECTION_BIDS.save(storage, (nft_collection_bid.nft_contract_address.as_str(), nft_collection_bid.bidder.as_str()), &nft_collection_bid)?;
+        // the price index was keyed by Decimal atomics, the bid is moved to its converted prices
+        unindex_collection_bid_prices(storage, &nft_collection_bid, &legacy_atomics)?;
+        index_collection_bid_prices(storage, &nft_collection_bid, &nft_collection_bid.bids_prices)?;
+    }
+
+    Ok((migrated, next))
+}
+
+fn migrate_trait_bid_prices(storage: &mut dyn Storage, namespace: &str, start_after: Option<Vec<u8>>, limit: u32, events: &mut Vec<Event>) -> Result<(u32, Option<Vec<u8>>), ContractError> {
+    let legacy_trait_bids = range_after(storage, &LEGACY_NFT_TRAIT_BIDS, start_after, limit)?;
+    let next = next_start_after(&legacy_trait_bids, limit);
+    let migrated = legacy_trait_bids.len() as u32;
+
+    for legacy_trait_bid in take_unmigrated(storage, namespace, legacy_trait_bids)? {
+        let entry = format!("trait_bid:{}:{}", legacy_trait_bid.nft_contract_address, legacy_trait_bid.id);
+        let nft_trait_bid = NftTraitBid {
+            id: legacy_trait_bid.id,
+            bidder: legacy_trait_bid.bidder,
+            price: legacy_price_to_usei(legacy_trait_bid.price, LegacyPriceRounding::Down, entry.as_str(), events)?,
+            nft_contract_address: legacy_trait_bid.nft_contract_address,
+            traits: legacy_trait_bid.traits,
+        };
+        NFT_TRAIT_BIDS.save(storage, (nft_trait_bid.nft_contract_address.as_str(), nft_trait_bid.id), &nft_trait_bid)?;
+    }
+
+    Ok((migrated, next))
+}
+
+fn migrate_token_set_bid_prices(storage: &mut dyn Storage, namespace: &str, start_after: Option<Vec<u8>>, limit: u32, events: &mut Vec<Event>) -> Result<(u32, Option<Vec<u8>>), ContractError> {
+    let legacy_token_set_bids = range_after(storage, &LEGACY_NFT_TOKEN_SET_BIDS, start_after, limit)?;
+    let next = next_start_after(&legacy_token_set_bids, limit);
+    let migrated = legacy_token_set_bids.len() as u32;
+
+    for legacy_token_set_bid in take_unmigrated(storage, namespace, legacy_token_set_bids)? {
+        let entry = format!("token_set_bid:{}:{}", legacy_token_set_bid.nft_contract_address, legacy_token_set_bid.id);
+        let nft_token_set_bid = NftTokenSetBid {
+            id: legacy_token_set_bid.id,
+            bidder: legacy_token_set_bid.bidder,
+            price: legacy_price_to_usei(legacy_token_set_bid.price, LegacyPriceRounding::Down, entry.as_str(), events)?,
+            nft_contract_address: legacy_token_set_bid.nft_contract_address,
+            merkle_root: legacy_token_set_bid.merkle_root,
+        };
+        NFT_TOKEN_SET_BIDS.save(storage, (nft_token_set_bid.nft_contract_address.as_str(), nft_token_set_bid.id), &nft_token_set_bid)?;
+    }
+
+    Ok((migrated, next))
+}
+
+fn migrate_bundle_prices(storage: &mut dyn Storage, namespace: &str, start_after: Option<Vec<u8>>, limit: u32, events: &mut Vec<Event>) -> Result<(u32, Option<Vec<u8>>), ContractError> {
+    let legacy_bundles = range_after(storage, &LEGACY_NFT_BUNDLES, start_after, limit)?;
+    let next = next_start_after(&legacy_bundles, limit);
+    let migrated = legacy_bundles.len() as u32;
+
+    for legacy_bundle in take_unmigrated(storage, namespace, legacy_bundles)? {
+        let entry = format!("bundle:{}", legacy_bundle.id);
+        let nft_bundle = NftBundle {
+            id: legacy_bundle.id,
+            lister: legacy_bundle.lister,
+            price: legacy_price_to_usei(legacy_bundle.price, LegacyPriceRounding::HalfUp, entry.as_str(), events)?,
+            items: legacy_bundle.items,
+            royalty_rule: legacy_bundle.royalty_rule,
+        };
+        NFT_BUNDLES.save(storage, nft_bundle.id, &nft_bundle)?;
+    }
+
+    Ok((migrated, next))
+}
@@ -1,33 +1,115 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Decimal, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
 
-use crate::state::{NftListing, NftBid, NftCollectionBid};
+use crate::state::{BundleRoyaltyRule, ClaimableBalance, CollectionFeeOverride, CollectionFreeze, CollectionInfo, Config, FeeSchedule, MigrationProgress, RoyaltyRecipient, NftBundle, NftListing, NftBid, NftCollectionBid, NftSwap, NftTokenSetBid, NftTraitBid, PaymentAsset, PayoutRecipientType, ReservationExpiry, TraitFilter};
 
+// admin defaults to the instantiator
 #[cw_serde]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    pub admin: Option<String>,
+}
+
+// sent to every registered hook contract as WasmMsg::Execute
+#[cw_serde]
+pub enum HookExecuteMsg {
+    TradeHook(TradeHookMsg),
+}
+
+#[cw_serde]
+pub enum TradeHookAction {
+    Sale,
+    Listing,
+    Cancel,
+}
+
+// price is in the payment asset, buyer is empty for listings and cancels
+// bundles notify per item with its share of the price, swaps notify per nft with price 0 as nfts are traded against each other
+#[cw_serde]
+pub struct TradeHookMsg {
+    pub action: TradeHookAction,
+    pub collection: String,
+    pub token_id: String,
+    pub price: Uint128,
+    pub payment_asset: PaymentAsset,
+    pub buyer: Option<String>,
+    pub seller: String,
+}
+
+#[cw_serde]
+pub enum SignedOrderSide {
+    // maker sells an nft they own and approved to the marketplace
+    Listing,
+    // maker buys the nft, price is drawn from the maker deposit balance
+    Offer,
+}
+
+// signed off-chain by the maker, see SignedOrderSignDoc for the signed bytes
+#[cw_serde]
+pub struct SignedOrder {
+    pub maker: String,
+    pub side: SignedOrderSide,
+    pub nft_contract_address: String,
+    pub token_id: String,
+    // in usei
+    pub price: Uint128,
+    pub expires_at: Timestamp,
+    pub nonce: u64,
+}
 
+// maker signs sha256 of the json encoding of this struct with secp256k1
+// chain_id keeps the signature from being replayed on another chain with the same contract address
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct SignedOrderSignDoc {
+    pub chain_id: String,
+    pub contract_address: String,
+    pub order: SignedOrder,
+}
+
+// admin has to be set when migrating from a version without config
+#[cw_serde]
+pub struct MigrateMsg {
+    pub admin: Option<String>,
+    // entries migrated in this transaction, the rest is migrated through ContinueMigration
+    // or a later migrate, which resumes an unfinished migration from where it stopped
+    pub limit: Option<u32>,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    // has to be executed with transferring nft to escrow in single transaction before this execution
-    // lister pays royalties and platform fee
+    // transfer the nft to escrow in the same transaction before this execution,
+    // or set escrowless to keep the nft in the lister wallet and approve the contract instead
+    // lister pays the maker fee out of the price, the buyer pays the taker fee and royalties on top
+    // reserved_for limits the buyer, after reservation_expires_at on_reservation_expiry applies (defaults to public)
+    // with fill_collection_bid the nft is sold right away to the best collection bid at or above price
+    // prices of listings and bids are in base units of the payment asset, e.g. usei
     List {
-        price: String,
+        price: Uint128,
         nft_contract_address: String,
         token_id: String,
+        reserved_for: Option<String>,
+        reservation_expires_at: Option<Timestamp>,
+        on_reservation_expiry: Option<ReservationExpiry>,
+        fill_collection_bid: Option<bool>,
+        escrowless: Option<bool>,
+        // price is in this native denom instead of usei, it has to be allowlisted
+        denom: Option<String>,
+        // price is in this cw20 instead of usei, it has to be allowlisted
+        cw20_contract_address: Option<String>,
     },
 
     Delist {
-        new_price: String,
+        new_price: Uint128,
         nft_contract_address: String,
         token_id: String,
     },
 
-    // send more sei than displayed in nft_listing.price to this execution to cover royalties and platform fee
+    // send more sei than displayed in nft_listing.price to this execution to cover royalties and the taker fee, the surplus is refunded
+    // referrer gets the configured share of the platform fee, same for every buy and sell message
     BuyListing {
         nft_contract_address: String,
         token_id: String,
+        referrer: Option<String>,
     },
 
     CancelListing {
@@ -35,17 +117,34 @@ pub enum ExecuteMsg {
         token_id: String,
     },
 
+    // anyone can return the nft to the lister once a reservation with return on expiry expired
+    ReturnExpiredListing {
+        nft_contract_address: String,
+        token_id: String,
+    },
+
+    // anyone can remove an escrowless listing once the lister no longer owns the nft or revoked the approval
+    InvalidateStaleListing {
+        nft_contract_address: String,
+        token_id: String,
+    },
+
 
-    // send sei in this execution via funds
-    // seller pays royalties and platform fee
+    // send sei or the allowlisted denom in this execution via funds, or set use_balance to pay from the usei deposit once the bid is filled
+    // seller pays royalties and both sides pay their platform fee out of the bid price
+    // a bid covering the listing price of the same nft with the taker fee and royalties is settled right away like a BuyListing,
+    // the rest of an escrowed bid goes back to the bidder, otherwise the bid stays on the book
     Bid {
-        price: String,
+        price: Uint128,
         nft_contract_address: String,
         token_id: String,
+        use_balance: Option<bool>,
+        // defaults to usei
+        denom: Option<String>,
     },
 
     UpdateBid {
-        new_price: String,
+        new_price: Uint128,
         nft_contract_address: String,
         token_id: String,
     },
@@ -55,18 +154,24 @@ pub enum ExecuteMsg {
         token_id: String,
     },
 
-    // has to be executed with transferring nft to escrow in single transaction before this execution
+    // the seller is the lister of record of the escrowed nft and the sale replaces the listing,
+    // or the seller owns the nft or is an operator of the owner and the contract is approved,
+    // an escrowed nft that no listing of the seller holds can't be sold
     SellToBid {
         nft_contract_address: String,
         token_id: String,
         bidder: String,
+        referrer: Option<String>,
     },
 
 
-    // send funds, total_amount = sum of prices
+    // send funds, total_amount = sum of prices, or set use_balance to pay from the usei deposit once a bid is filled
     CollectionBid {
-        prices: Vec<String>,
+        prices: Vec<Uint128>,
         nft_contract_address: String,
+        use_balance: Option<bool>,
+        // defaults to usei, has to match the denom of earlier collection bids of the bidder
+        denom: Option<String>,
     },
 
     CancelAllCollectionBids {
@@ -75,15 +180,344 @@ pub enum ExecuteMsg {
 
     CancelCollectionBid {
         nft_contract_address: String,
-        price: String,
+        price: Uint128,
     },
 
-    // has to be executed with transferring nft to escrow in single transaction before this execution
+    // the seller is the lister of record of the escrowed nft and the sale replaces the listing,
+    // or the seller owns the nft or is an operator of the owner and the contract is approved,
+    // an escrowed nft that no listing of the seller holds can't be sold
     SellToCollectionBid {
         nft_contract_address: String,
         token_id: String,
         bidder: String,
-        price: String,
+        price: Uint128,
+        referrer: Option<String>,
+    },
+
+
+    // send usei equal to price, nft has to have every trait in its cw721 metadata attributes
+    TraitBid {
+        price: Uint128,
+        nft_contract_address: String,
+        traits: Vec<TraitFilter>,
+    },
+
+    CancelTraitBid {
+        nft_contract_address: String,
+        trait_bid_id: u64,
+    },
+
+    // the seller is the lister of record of the escrowed nft and the sale replaces the listing,
+    // or the seller owns the nft or is an operator of the owner and the contract is approved,
+    // an escrowed nft that no listing of the seller holds can't be sold
+    SellToTraitBid {
+        nft_contract_address: String,
+        token_id: String,
+        trait_bid_id: u64,
+        referrer: Option<String>,
+    },
+
+
+    // send usei equal to price, merkle_root commits to sha256(token_id) of every eligible token
+    TokenSetBid {
+        price: Uint128,
+        nft_contract_address: String,
+        merkle_root: Binary,
+    },
+
+    CancelTokenSetBid {
+        nft_contract_address: String,
+        token_set_bid_id: u64,
+    },
+
+    // the seller is the lister of record of the escrowed nft and the sale replaces the listing,
+    // or the seller owns the nft or is an operator of the owner and the contract is approved,
+    // an escrowed nft that no listing of the seller holds can't be sold
+    SellToTokenSetBid {
+        nft_contract_address: String,
+        token_id: String,
+        token_set_bid_id: u64,
+        proof: Vec<Binary>,
+        referrer: Option<String>,
+    },
+
+
+    // has to be executed with transferring every nft to escrow in single transaction before this execution
+    // items are (nft contract address, token id), royalty_rule defaults to equal split, price is in usei
+    ListBundle {
+        items: Vec<(String, String)>,
+        price: Uint128,
+        royalty_rule: Option<BundleRoyaltyRule>,
+    },
+
+    // send price plus platform fee and royalties via funds
+    BuyBundle {
+        bundle_id: u64,
+        referrer: Option<String>,
+    },
+
+    CancelBundle {
+        bundle_id: u64,
+    },
+
+
+    // has to be executed with transferring offered nfts to escrow in single transaction before this execution
+    // optionally send usei via funds on top of the offered nfts
+    // items are (nft contract address, token id)
+    CreateSwap {
+        offered_nfts: Vec<(String, String)>,
+        requested_nfts: Vec<(String, String)>,
+        counterparty: Option<String>,
+    },
+
+    // has to be executed with transferring requested nfts to escrow in single transaction before this execution
+    AcceptSwap {
+        swap_id: u64,
+    },
+
+    CancelSwap {
+        swap_id: u64,
+    },
+
+
+    // send sei via funds, balance funded bids are paid from the deposit when filled
+    Deposit {},
+
+    Withdraw {
+        amount: Uint128,
+    },
+
+
+    // listing: send price, taker fee and royalties via funds, the surplus is refunded
+    // offer: like SellToBid, the nft is escrowed for a listing of the taker, which the sale replaces,
+    // or the taker owns the nft or is an operator of the owner and the contract is approved,
+    // the seller pays royalties and both sides pay their platform fee out of the price taken from the maker deposit
+    FillSignedOrder {
+        order: SignedOrder,
+        signature: Binary,
+        maker_pubkey: Binary,
+        referrer: Option<String>,
+    },
+
+    CancelSignedOrder {
+        order: SignedOrder,
+    },
+
+    // invalidates every signed order of the sender with a lower nonce
+    IncrementNonce {},
+
+    // admin only
+    UpdateAdmin {
+        admin: String,
+    },
+
+    // admin only, share of the platform fee paid to referrers, between 0 and 1
+    UpdateReferralFeeShare {
+        referral_fee_share: Decimal,
+    },
+
+    // collection minter or owner only, shares are parts of the sale price and can't exceed 25% in total
+    // replaces the royalties of the nft contract for every sale in the collection
+    SetCollectionRoyalties {
+        nft_contract_address: String,
+        recipients: Vec<(String, Decimal)>,
+    },
+
+    // collection minter or owner only
+    RemoveCollectionRoyalties {
+        nft_contract_address: String,
+    },
+
+    // admin only, rates can't exceed 10% and tiers have to be sorted by increasing min_volume
+    UpdateFeeSchedule {
+        fee_schedule: FeeSchedule,
+    },
+
+    // admin only, replaces maker and taker rates of every sale in the collection between starts_at and ends_at,
+    // fee is the total rate of the sale and charged once, on the taker side
+    SetCollectionFeeOverride {
+        nft_contract_address: String,
+        fee: Decimal,
+        starts_at: Option<Timestamp>,
+        ends_at: Option<Timestamp>,
+    },
+
+    // admin only
+    RemoveCollectionFeeOverride {
+        nft_contract_address: String,
+    },
+
+    // admin only, hook contract has to handle HookExecuteMsg, a failing hook doesn't revert the trade
+    AddHook {
+        contract_address: String,
+    },
+
+    // admin only
+    RemoveHook {
+        contract_address: String,
+    },
+
+    // admin only, gas limit of every hook sub message, has to be above 0
+    UpdateHookGasLimit {
+        hook_gas_limit: u64,
+    },
+
+    // sent by an allowlisted cw20 contract, msg is a ReceiveMsg paid with the received tokens
+    Receive(Cw20ReceiveMsg),
+
+    // admin only, allows listings and bids priced in the cw20
+    AllowCw20 {
+        contract_address: String,
+    },
+
+    // admin only, existing listings and bids in the cw20 stay valid
+    DisallowCw20 {
+        contract_address: String,
+    },
+
+    // admin only, allows listings and bids priced in the native denom, usei is always allowed
+    AllowDenom {
+        denom: String,
+    },
+
+    // admin only, existing listings and bids in the denom stay valid
+    DisallowDenom {
+        denom: String,
+    },
+
+    // migrates the next limit entries of a paginated migration
+    ContinueMigration {
+        limit: Option<u32>,
+    },
+
+    // admin only, verification status and display metadata of the collection
+    SetCollectionInfo {
+        nft_contract_address: String,
+        collection_info: CollectionInfo,
+    },
+
+    // admin only
+    RemoveCollectionInfo {
+        nft_contract_address: String,
+    },
+
+    // admin only, in curation mode only verified collections can be traded
+    UpdateCurationMode {
+        curation_mode: bool,
+    },
+
+    // collection minter or owner only, disables listing, bidding and selling until unfreeze_at or until unfrozen
+    FreezeCollectionTrading {
+        nft_contract_address: String,
+        unfreeze_at: Option<Timestamp>,
+    },
+
+    // collection minter or owner only
+    UnfreezeCollectionTrading {
+        nft_contract_address: String,
+    },
+
+    // admin only, seconds before an nft can be sold back to the address it was bought from, 0 disables the cooldown
+    UpdateResaleCooldown {
+        resale_cooldown_seconds: u64,
+    },
+
+    // admin only, pull credits the proceeds of the recipient type to claimable balances, push sends them right away
+    UpdatePayoutMode {
+        recipient_type: PayoutRecipientType,
+        pull: bool,
+    },
+
+    // sends all claimable balances of the sender
+    Claim {},
+
+    // admin only
+    UpdateModerators {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    // admin or moderator, blocked addresses can only cancel their orders and withdraw or claim their funds
+    // and can't be the counterparty of a sale, addresses blocked through sudo can only be removed through sudo
+    UpdateAddressBlocklist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    // admin or moderator, (nft contract address, token id) pairs reported stolen
+    UpdateNftBlocklist {
+        add: Vec<(String, String)>,
+        remove: Vec<(String, String)>,
+    },
+
+    // admin only, unblocks the nft, closes its orders and sends it from escrow to the recipient
+    ResolveBlockedNft {
+        nft_contract_address: String,
+        token_id: String,
+        recipient: String,
+    },
+}
+
+// executed by the chain, e.g. through a governance proposal
+#[cw_serde]
+pub enum SudoMsg {
+    // same validation as UpdateFeeSchedule
+    UpdateFeeSchedule {
+        fee_schedule: FeeSchedule,
+    },
+
+    // blocks every execution until unpaused, sudo messages still work
+    Pause {},
+
+    Unpause {},
+
+    // blocked addresses can only cancel their orders and withdraw or claim their funds
+    UpdateBlocklist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    // removes the listing and returns an escrowed nft to the lister
+    ForceReturnListing {
+        nft_contract_address: String,
+        token_id: String,
+    },
+
+    // removes the bid and returns the escrowed payment to the bidder
+    ForceReturnBid {
+        nft_contract_address: String,
+        token_id: String,
+        bidder: String,
+    },
+
+    // removes all collection bids of the bidder and returns the escrowed payment
+    ForceReturnCollectionBids {
+        nft_contract_address: String,
+        bidder: String,
+    },
+}
+
+// same as the ExecuteMsg variants but paid in the cw20 that sent the tokens
+#[cw_serde]
+pub enum ReceiveMsg {
+    // amount has to cover price, taker fee and royalties
+    BuyListing {
+        nft_contract_address: String,
+        token_id: String,
+        referrer: Option<String>,
+    },
+
+    // amount has to cover the price
+    Bid {
+        price: Uint128,
+        nft_contract_address: String,
+        token_id: String,
+    },
+
+    // amount has to cover the sum of prices
+    CollectionBid {
+        prices: Vec<Uint128>,
+        nft_contract_address: String,
     },
 }
 
@@ -96,6 +530,14 @@ pub enum QueryMsg {
         token_id: String,
     },
 
+    #[returns(GetPaginatedReservedListingsResponse)]
+    GetPaginatedReservedListings {
+        buyer: String,
+        // (nft contract address, token id)
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+
     #[returns(GetNftBidResponse)]
     GetNftBid {
         nft_contract_address: String,
@@ -114,6 +556,8 @@ pub enum QueryMsg {
         nft_contract_address: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        // denom or cw20 contract address
+        denom: Option<String>,
     },
 
     #[returns(GetPaginatedBidsResponse)]
@@ -122,6 +566,8 @@ pub enum QueryMsg {
         token_id: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        // denom or cw20 contract address
+        denom: Option<String>,
     },
 
     #[returns(GetPaginatedCollectionBidsResponse)]
@@ -129,6 +575,160 @@ pub enum QueryMsg {
         nft_contract_address: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        // denom or cw20 contract address
+        denom: Option<String>,
+    },
+
+    #[returns(GetNftTraitBidResponse)]
+    GetNftTraitBid {
+        nft_contract_address: String,
+        trait_bid_id: u64,
+    },
+
+    #[returns(GetPaginatedTraitBidsResponse)]
+    GetPaginatedTraitBids {
+        nft_contract_address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetNftTokenSetBidResponse)]
+    GetNftTokenSetBid {
+        nft_contract_address: String,
+        token_set_bid_id: u64,
+    },
+
+    #[returns(GetPaginatedTokenSetBidsResponse)]
+    GetPaginatedTokenSetBids {
+        nft_contract_address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetNftBundleResponse)]
+    GetNftBundle {
+        bundle_id: u64,
+    },
+
+    #[returns(GetPaginatedBundlesResponse)]
+    GetPaginatedBundles {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetNftSwapResponse)]
+    GetNftSwap {
+        swap_id: u64,
+    },
+
+    #[returns(GetPaginatedSwapsResponse)]
+    GetPaginatedSwapsByMaker {
+        maker: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetPaginatedSwapsResponse)]
+    GetPaginatedSwapsByTaker {
+        taker: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetBalanceResponse)]
+    GetBalance {
+        account: String,
+    },
+
+    #[returns(GetMakerNonceResponse)]
+    GetMakerNonce {
+        maker: String,
+    },
+
+    #[returns(GetSignedOrderStatusResponse)]
+    GetSignedOrderStatus {
+        order: SignedOrder,
+    },
+
+    #[returns(GetConfigResponse)]
+    GetConfig {},
+
+    #[returns(GetHooksResponse)]
+    GetHooks {},
+
+    // platform fee rates of the account for its trailing 30 day volume
+    #[returns(GetEffectiveFeeResponse)]
+    GetEffectiveFee {
+        account: String,
+    },
+
+    #[returns(GetPaginatedCollectionFeeOverridesResponse)]
+    GetPaginatedCollectionFeeOverrides {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetCollectionRoyaltiesResponse)]
+    GetCollectionRoyalties {
+        nft_contract_address: String,
+    },
+
+    #[returns(GetReferralEarningsResponse)]
+    GetReferralEarnings {
+        referrer: String,
+    },
+
+    #[returns(GetCw20AllowlistResponse)]
+    GetCw20Allowlist {},
+
+    #[returns(GetDenomAllowlistResponse)]
+    GetDenomAllowlist {},
+
+    #[returns(GetMigrationProgressResponse)]
+    GetMigrationProgress {},
+
+    #[returns(GetCollectionInfoResponse)]
+    GetCollectionInfo {
+        nft_contract_address: String,
+    },
+
+    #[returns(GetPaginatedVerifiedCollectionsResponse)]
+    GetPaginatedVerifiedCollections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetCollectionFreezeResponse)]
+    GetCollectionFreeze {
+        nft_contract_address: String,
+    },
+
+    #[returns(GetClaimableBalancesResponse)]
+    GetClaimableBalances {
+        recipient: String,
+    },
+
+    #[returns(GetModeratorsResponse)]
+    GetModerators {},
+
+    // addresses blocked through sudo
+    #[returns(GetPaginatedBlockedAddressesResponse)]
+    GetPaginatedBlockedAddresses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // addresses blocked by the admin or a moderator
+    #[returns(GetPaginatedBlockedAddressesResponse)]
+    GetPaginatedModeratedBlockedAddresses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetPaginatedBlockedNftsResponse)]
+    GetPaginatedBlockedNfts {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
     },
 }
 
@@ -152,6 +752,11 @@ pub struct GetPaginatedListingsResponse {
     pub listings: Vec<(String, NftListing)>,
 }
 
+#[cw_serde]
+pub struct GetPaginatedReservedListingsResponse {
+    pub listings: Vec<NftListing>,
+}
+
 #[cw_serde]
 pub struct GetPaginatedBidsResponse {
     pub bids: Vec<(String, NftBid)>,
@@ -160,4 +765,147 @@ pub struct GetPaginatedBidsResponse {
 #[cw_serde]
 pub struct GetPaginatedCollectionBidsResponse {
     pub collection_bids: Vec<(String, NftCollectionBid)>,
-}
\ No newline at end of file
+}
+
+#[cw_serde]
+pub struct GetNftTraitBidResponse {
+    pub nft_trait_bid: NftTraitBid,
+}
+
+#[cw_serde]
+pub struct GetPaginatedTraitBidsResponse {
+    pub trait_bids: Vec<(u64, NftTraitBid)>,
+}
+
+#[cw_serde]
+pub struct GetNftTokenSetBidResponse {
+    pub nft_token_set_bid: NftTokenSetBid,
+}
+
+#[cw_serde]
+pub struct GetPaginatedTokenSetBidsResponse {
+    pub token_set_bids: Vec<(u64, NftTokenSetBid)>,
+}
+
+#[cw_serde]
+pub struct GetNftBundleResponse {
+    pub nft_bundle: NftBundle,
+}
+
+#[cw_serde]
+pub struct GetPaginatedBundlesResponse {
+    pub bundles: Vec<(u64, NftBundle)>,
+}
+
+#[cw_serde]
+pub struct GetNftSwapResponse {
+    pub nft_swap: NftSwap,
+}
+
+#[cw_serde]
+pub struct GetPaginatedSwapsResponse {
+    pub swaps: Vec<(u64, NftSwap)>,
+}
+
+#[cw_serde]
+pub struct GetBalanceResponse {
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct GetMakerNonceResponse {
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct GetSignedOrderStatusResponse {
+    pub order_hash: Binary,
+    pub cancelled_or_filled: bool,
+}
+
+#[cw_serde]
+pub struct GetConfigResponse {
+    pub config: Config,
+    // set by governance through sudo
+    pub paused: bool,
+}
+
+#[cw_serde]
+pub struct GetHooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[cw_serde]
+pub struct GetReferralEarningsResponse {
+    // (payment asset id, amount)
+    pub earnings: Vec<(String, Uint128)>,
+}
+
+#[cw_serde]
+pub struct GetCw20AllowlistResponse {
+    pub cw20_contract_addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct GetDenomAllowlistResponse {
+    pub denoms: Vec<String>,
+}
+
+#[cw_serde]
+pub struct GetCollectionInfoResponse {
+    pub collection_info: CollectionInfo,
+}
+
+#[cw_serde]
+pub struct GetPaginatedVerifiedCollectionsResponse {
+    pub collections: Vec<(String, CollectionInfo)>,
+}
+
+#[cw_serde]
+pub struct GetCollectionFreezeResponse {
+    pub collection_freeze: Option<CollectionFreeze>,
+    pub is_frozen: bool,
+}
+
+#[cw_serde]
+pub struct GetClaimableBalancesResponse {
+    pub claimable_balances: Vec<ClaimableBalance>,
+}
+
+#[cw_serde]
+pub struct GetModeratorsResponse {
+    pub moderators: Vec<String>,
+}
+
+#[cw_serde]
+pub struct GetPaginatedBlockedAddressesResponse {
+    pub addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct GetPaginatedBlockedNftsResponse {
+    pub nfts: Vec<(String, String)>,
+}
+
+#[cw_serde]
+pub struct GetMigrationProgressResponse {
+    // empty when no migration is running
+    pub progress: Option<MigrationProgress>,
+}
+
+#[cw_serde]
+pub struct GetEffectiveFeeResponse {
+    pub maker_fee: Decimal,
+    pub taker_fee: Decimal,
+    pub volume: Uint128,
+}
+
+#[cw_serde]
+pub struct GetPaginatedCollectionFeeOverridesResponse {
+    pub collection_fee_overrides: Vec<(String, CollectionFeeOverride)>,
+}
+
+#[cw_serde]
+pub struct GetCollectionRoyaltiesResponse {
+    pub recipients: Vec<RoyaltyRecipient>,
+}
@@ -1,7 +1,7 @@
-use cosmwasm_std::{Deps, Order, StdResult};
+use cosmwasm_std::{Deps, Env, Order, StdResult, Uint128};
 use cw_storage_plus::Bound;
 
-use crate::{msg::{GetNftBidResponse, GetNftCollectionBidResponse, GetNftListingResponse, GetPaginatedBidsResponse, GetPaginatedCollectionBidsResponse, GetPaginatedListingsResponse}, state::{NFT_BIDS, NFT_COLLECTION_BIDS, NFT_LISTINGS}};
+use crate::{execute::{fees::{effective_fees, trailing_volume}, signed_order::signed_order_hash}, msg::{GetBalanceResponse, GetClaimableBalancesResponse, GetCollectionFreezeResponse, GetCollectionInfoResponse, GetCollectionRoyaltiesResponse, GetConfigResponse, GetCw20AllowlistResponse, GetDenomAllowlistResponse, GetMigrationProgressResponse, GetModeratorsResponse, GetPaginatedBlockedAddressesResponse, GetPaginatedBlockedNftsResponse, GetPaginatedCollectionFeeOverridesResponse, GetEffectiveFeeResponse, GetHooksResponse, GetMakerNonceResponse, GetNftBidResponse, GetNftBundleResponse, GetNftCollectionBidResponse, GetNftListingResponse, GetNftSwapResponse, GetNftTokenSetBidResponse, GetNftTraitBidResponse, GetPaginatedBidsResponse, GetPaginatedBundlesResponse, GetPaginatedCollectionBidsResponse, GetPaginatedListingsResponse, GetPaginatedReservedListingsResponse, GetPaginatedSwapsResponse, GetPaginatedTokenSetBidsResponse, GetPaginatedTraitBidsResponse, GetPaginatedVerifiedCollectionsResponse, GetReferralEarningsResponse, GetSignedOrderStatusResponse, SignedOrder}, state::{BALANCES, BLOCKED_NFTS, BLOCKLIST, CLAIMABLE_BALANCES, COLLECTIONS, COLLECTION_FREEZES, COLLECTION_FEE_OVERRIDES, CONFIG, CW20_ALLOWLIST, DENOM_ALLOWLIST, HOOKS, MAKER_NONCES, MIGRATION_PROGRESS, MODERATED_BLOCKLIST, MODERATORS, NFT_BIDS, NFT_BUNDLES, NFT_COLLECTION_BIDS, NFT_LISTINGS, NFT_RESERVED_LISTINGS, NFT_SWAPS, NFT_SWAPS_BY_MAKER, NFT_SWAPS_BY_TAKER, NFT_TOKEN_SET_BIDS, NFT_TRAIT_BIDS, PAUSED, REFERRAL_EARNINGS, ROYALTY_REGISTRY, SIGNED_ORDERS_CANCELLED_OR_FILLED}};
 
 
 pub fn get_nft_listing(deps: Deps, nft_contract_address: String, token_id: String) -> StdResult<GetNftListingResponse> {
@@ -27,17 +27,43 @@ pub fn get_nft_collection_bid(deps: Deps, nft_contract_address: String, bidder:
     Ok(GetNftCollectionBidResponse{ nft_collection_bid })
 }
 
+pub fn get_nft_trait_bid(deps: Deps, nft_contract_address: String, trait_bid_id: u64) -> StdResult<GetNftTraitBidResponse> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())?;
+    let key = (nft_contract_address.as_str(), trait_bid_id);
+    let nft_trait_bid = NFT_TRAIT_BIDS.load(deps.storage, key)?;
+    Ok(GetNftTraitBidResponse{ nft_trait_bid })
+}
+
+pub fn get_nft_token_set_bid(deps: Deps, nft_contract_address: String, token_set_bid_id: u64) -> StdResult<GetNftTokenSetBidResponse> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())?;
+    let key = (nft_contract_address.as_str(), token_set_bid_id);
+    let nft_token_set_bid = NFT_TOKEN_SET_BIDS.load(deps.storage, key)?;
+    Ok(GetNftTokenSetBidResponse{ nft_token_set_bid })
+}
+
+pub fn get_nft_bundle(deps: Deps, bundle_id: u64) -> StdResult<GetNftBundleResponse> {
+    let nft_bundle = NFT_BUNDLES.load(deps.storage, bundle_id)?;
+    Ok(GetNftBundleResponse{ nft_bundle })
+}
+
+pub fn get_nft_swap(deps: Deps, swap_id: u64) -> StdResult<GetNftSwapResponse> {
+    let nft_swap = NFT_SWAPS.load(deps.storage, swap_id)?;
+    Ok(GetNftSwapResponse{ nft_swap })
+}
+
 
 pub fn query_paginated_listings(
     deps: Deps,
     nft_contract_address: String,
     start_after: Option<&str>,
     limit: Option<u32>,
+    denom: Option<String>,
 ) -> StdResult<GetPaginatedListingsResponse> {
-    let start_bound = start_after.map(|id| Bound::exclusive(id));
+    let start_bound = start_after.map(Bound::exclusive);
     let listings: StdResult<Vec<_>> = NFT_LISTINGS
         .prefix(nft_contract_address.as_str())
         .range(deps.storage, start_bound, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, listing)| denom.as_deref().is_none_or(|denom| listing.payment_asset.id() == denom)))
         .take(limit.unwrap_or(10) as usize)
         .collect();
 
@@ -46,17 +72,43 @@ pub fn query_paginated_listings(
     })
 }
 
+pub fn query_paginated_reserved_listings(
+    deps: Deps,
+    buyer: String,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedReservedListingsResponse> {
+    let start_bound = start_after
+        .as_ref()
+        .map(|(nft_contract_address, token_id)| Bound::exclusive((nft_contract_address.as_str(), token_id.as_str())));
+    let listings: StdResult<Vec<_>> = NFT_RESERVED_LISTINGS
+        .sub_prefix(buyer.as_str())
+        .keys(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|key| {
+            let (nft_contract_address, token_id) = key?;
+            NFT_LISTINGS.load(deps.storage, (nft_contract_address.as_str(), token_id.as_str()))
+        })
+        .collect();
+
+    Ok(GetPaginatedReservedListingsResponse {
+        listings: listings?,
+    })
+}
+
 pub fn query_paginated_bids(
     deps: Deps,
     nft_contract_address: String,
     token_id: String,
     start_after: Option<&str>,
     limit: Option<u32>,
+    denom: Option<String>,
 ) -> StdResult<GetPaginatedBidsResponse> {
-    let start_bound = start_after.map(|id| Bound::exclusive(id));
+    let start_bound = start_after.map(Bound::exclusive);
     let bids: StdResult<Vec<_>> = NFT_BIDS
         .prefix((nft_contract_address.as_str(), token_id.as_str()))
         .range(deps.storage, start_bound, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, bid)| denom.as_deref().is_none_or(|denom| bid.payment_asset.id() == denom)))
         .take(limit.unwrap_or(10) as usize)
         .collect();
 
@@ -70,15 +122,297 @@ pub fn query_paginated_collection_bids(
     nft_contract_address: String,
     start_after: Option<&str>,
     limit: Option<u32>,
+    denom: Option<String>,
 ) -> StdResult<GetPaginatedCollectionBidsResponse> {
-    let start_bound = start_after.map(|id| Bound::exclusive(id));
+    let start_bound = start_after.map(Bound::exclusive);
     let collection_bids: StdResult<Vec<_>> = NFT_COLLECTION_BIDS
         .prefix(nft_contract_address.as_str())
         .range(deps.storage, start_bound, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, collection_bid)| denom.as_deref().is_none_or(|denom| collection_bid.payment_asset.id() == denom)))
         .take(limit.unwrap_or(10) as usize)
         .collect();
 
     Ok(GetPaginatedCollectionBidsResponse {
         collection_bids: collection_bids?,
     })
-}
\ No newline at end of file
+}
+
+pub fn query_paginated_trait_bids(
+    deps: Deps,
+    nft_contract_address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedTraitBidsResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let trait_bids: StdResult<Vec<_>> = NFT_TRAIT_BIDS
+        .prefix(nft_contract_address.as_str())
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedTraitBidsResponse {
+        trait_bids: trait_bids?,
+    })
+}
+
+pub fn query_paginated_token_set_bids(
+    deps: Deps,
+    nft_contract_address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedTokenSetBidsResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let token_set_bids: StdResult<Vec<_>> = NFT_TOKEN_SET_BIDS
+        .prefix(nft_contract_address.as_str())
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedTokenSetBidsResponse {
+        token_set_bids: token_set_bids?,
+    })
+}
+
+pub fn query_paginated_bundles(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedBundlesResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let bundles: StdResult<Vec<_>> = NFT_BUNDLES
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedBundlesResponse {
+        bundles: bundles?,
+    })
+}
+
+pub fn query_paginated_swaps_by_maker(
+    deps: Deps,
+    maker: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedSwapsResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let swaps: StdResult<Vec<_>> = NFT_SWAPS_BY_MAKER
+        .prefix(maker.as_str())
+        .keys(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|swap_id| {
+            let swap_id = swap_id?;
+            Ok((swap_id, NFT_SWAPS.load(deps.storage, swap_id)?))
+        })
+        .collect();
+
+    Ok(GetPaginatedSwapsResponse {
+        swaps: swaps?,
+    })
+}
+
+pub fn query_paginated_swaps_by_taker(
+    deps: Deps,
+    taker: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedSwapsResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let swaps: StdResult<Vec<_>> = NFT_SWAPS_BY_TAKER
+        .prefix(taker.as_str())
+        .keys(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|swap_id| {
+            let swap_id = swap_id?;
+            Ok((swap_id, NFT_SWAPS.load(deps.storage, swap_id)?))
+        })
+        .collect();
+
+    Ok(GetPaginatedSwapsResponse {
+        swaps: swaps?,
+    })
+}
+
+pub fn get_balance(deps: Deps, account: String) -> StdResult<GetBalanceResponse> {
+    let account = deps.api.addr_validate(account.as_str())?;
+    let balance = BALANCES.may_load(deps.storage, account.as_str())?.unwrap_or_default();
+    Ok(GetBalanceResponse{ balance })
+}
+
+pub fn get_maker_nonce(deps: Deps, maker: String) -> StdResult<GetMakerNonceResponse> {
+    let maker = deps.api.addr_validate(maker.as_str())?;
+    let nonce = MAKER_NONCES.may_load(deps.storage, maker.as_str())?.unwrap_or_default();
+    Ok(GetMakerNonceResponse{ nonce })
+}
+
+pub fn get_signed_order_status(deps: Deps, env: Env, order: SignedOrder) -> StdResult<GetSignedOrderStatusResponse> {
+    let order_hash = signed_order_hash(&env, &order)?;
+    let cancelled_or_filled = SIGNED_ORDERS_CANCELLED_OR_FILLED.has(deps.storage, order_hash.as_slice());
+    Ok(GetSignedOrderStatusResponse{ order_hash, cancelled_or_filled })
+}
+
+pub fn get_config(deps: Deps) -> StdResult<GetConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let paused = PAUSED.may_load(deps.storage)?.unwrap_or(false);
+    Ok(GetConfigResponse{ config, paused })
+}
+
+pub fn get_hooks(deps: Deps) -> StdResult<GetHooksResponse> {
+    let hooks = HOOKS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(GetHooksResponse{ hooks })
+}
+
+pub fn get_referral_earnings(deps: Deps, referrer: String) -> StdResult<GetReferralEarningsResponse> {
+    let referrer = deps.api.addr_validate(referrer.as_str())?;
+    let earnings = REFERRAL_EARNINGS
+        .prefix(referrer.as_str())
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+    Ok(GetReferralEarningsResponse{ earnings })
+}
+
+pub fn get_cw20_allowlist(deps: Deps) -> StdResult<GetCw20AllowlistResponse> {
+    let cw20_contract_addresses = CW20_ALLOWLIST
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(GetCw20AllowlistResponse{ cw20_contract_addresses })
+}
+
+pub fn get_denom_allowlist(deps: Deps) -> StdResult<GetDenomAllowlistResponse> {
+    let denoms = DENOM_ALLOWLIST
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(GetDenomAllowlistResponse{ denoms })
+}
+
+pub fn get_collection_info(deps: Deps, nft_contract_address: String) -> StdResult<GetCollectionInfoResponse> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())?;
+    let collection_info = COLLECTIONS.load(deps.storage, nft_contract_address.as_str())?;
+    Ok(GetCollectionInfoResponse{ collection_info })
+}
+
+pub fn query_paginated_verified_collections(
+    deps: Deps,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedVerifiedCollectionsResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let collections: StdResult<Vec<_>> = COLLECTIONS
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, collection_info)| collection_info.verified))
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedVerifiedCollectionsResponse {
+        collections: collections?,
+    })
+}
+
+pub fn get_collection_freeze(deps: Deps, env: Env, nft_contract_address: String) -> StdResult<GetCollectionFreezeResponse> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())?;
+    let collection_freeze = COLLECTION_FREEZES.may_load(deps.storage, nft_contract_address.as_str())?;
+    let is_frozen = collection_freeze.as_ref().is_some_and(|collection_freeze| collection_freeze.is_frozen(env.block.time));
+    Ok(GetCollectionFreezeResponse{ collection_freeze, is_frozen })
+}
+
+pub fn get_claimable_balances(deps: Deps, recipient: String) -> StdResult<GetClaimableBalancesResponse> {
+    let claimable_balances = CLAIMABLE_BALANCES
+        .prefix(recipient.as_str())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, claimable_balance)| claimable_balance))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(GetClaimableBalancesResponse{ claimable_balances })
+}
+
+pub fn get_moderators(deps: Deps) -> StdResult<GetModeratorsResponse> {
+    let moderators = MODERATORS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(GetModeratorsResponse{ moderators })
+}
+
+pub fn query_paginated_blocked_addresses(
+    deps: Deps,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedBlockedAddressesResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let addresses: StdResult<Vec<_>> = BLOCKLIST
+        .keys(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedBlockedAddressesResponse {
+        addresses: addresses?,
+    })
+}
+
+pub fn query_paginated_moderated_blocked_addresses(
+    deps: Deps,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedBlockedAddressesResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let addresses: StdResult<Vec<_>> = MODERATED_BLOCKLIST
+        .keys(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedBlockedAddressesResponse {
+        addresses: addresses?,
+    })
+}
+
+pub fn query_paginated_blocked_nfts(
+    deps: Deps,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedBlockedNftsResponse> {
+    let start_bound = start_after
+        .as_ref()
+        .map(|(nft_contract_address, token_id)| Bound::exclusive((nft_contract_address.as_str(), token_id.as_str())));
+    let nfts: StdResult<Vec<_>> = BLOCKED_NFTS
+        .keys(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedBlockedNftsResponse {
+        nfts: nfts?,
+    })
+}
+
+pub fn get_migration_progress(deps: Deps) -> StdResult<GetMigrationProgressResponse> {
+    let progress = MIGRATION_PROGRESS.may_load(deps.storage)?;
+    Ok(GetMigrationProgressResponse{ progress })
+}
+
+pub fn get_effective_fee(deps: Deps, env: Env, account: String) -> StdResult<GetEffectiveFeeResponse> {
+    let account = deps.api.addr_validate(account.as_str())?;
+    let (maker_fee, taker_fee) = effective_fees(deps.storage, &env, account.as_str())?;
+    let volume = trailing_volume(deps.storage, &env, account.as_str())?;
+    Ok(GetEffectiveFeeResponse{ maker_fee, taker_fee, volume })
+}
+
+pub fn query_paginated_collection_fee_overrides(
+    deps: Deps,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> StdResult<GetPaginatedCollectionFeeOverridesResponse> {
+    let start_bound = start_after.map(Bound::exclusive);
+    let collection_fee_overrides: StdResult<Vec<_>> = COLLECTION_FEE_OVERRIDES
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(limit.unwrap_or(10) as usize)
+        .collect();
+
+    Ok(GetPaginatedCollectionFeeOverridesResponse {
+        collection_fee_overrides: collection_fee_overrides?,
+    })
+}
+
+pub fn get_collection_royalties(deps: Deps, nft_contract_address: String) -> StdResult<GetCollectionRoyaltiesResponse> {
+    let nft_contract_address = deps.api.addr_validate(nft_contract_address.as_str())?;
+    let recipients = ROYALTY_REGISTRY.load(deps.storage, nft_contract_address.as_str())?;
+    Ok(GetCollectionRoyaltiesResponse{ recipients })
+}
@@ -1,29 +1,114 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Decimal};
-use cw_storage_plus::Map;
+use cosmwasm_std::{Addr, Binary, Decimal, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
 
 pub const PLATFORM_FEE_RECEIVER: &str = "sei153kttlsjk7vm9gkjf0sykx6el6yyhddq0w3zea";
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct NftListing {
     pub lister: Addr,
-    pub price: Decimal,
+    // in base units of the payment asset
+    pub price: Uint128,
     pub nft_contract_address: Addr,
     pub token_id: String,
+    pub reservation: Option<ListingReservation>,
+    #[serde(default)]
+    pub custody: ListingCustody,
+    #[serde(default)]
+    pub payment_asset: PaymentAsset,
+}
+
+// asset the price is denominated in, every payout of the trade is sent in it
+// only listings, bids and collection bids carry one, bundles, swaps, trait and token set bids, signed orders
+// and deposits keep settling in the default usei
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentAsset {
+    Native { denom: String },
+    Cw20 { contract_address: Addr },
+}
+
+impl Default for PaymentAsset {
+    fn default() -> Self {
+        PaymentAsset::Native { denom: "usei".to_string() }
+    }
+}
+
+impl PaymentAsset {
+    // denom or cw20 contract address
+    pub fn id(&self) -> &str {
+        match self {
+            PaymentAsset::Native { denom } => denom.as_str(),
+            PaymentAsset::Cw20 { contract_address } => contract_address.as_str(),
+        }
+    }
+}
+
+// where the listed nft is kept until it is sold
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingCustody {
+    // transferred to the contract before listing
+    #[default]
+    Escrow,
+    // stays in the lister wallet, the contract is approved to transfer it
+    Approval,
 }
 
 // key: (nft contract address, nft token id)
 pub const NFT_LISTINGS: Map<(&str, &str), NftListing> = Map::new("nft_listings");
 
+// what happens with a reserved listing once the reservation expires
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationExpiry {
+    // anyone can buy the listing
+    Public,
+    // nobody can buy the listing and anyone can return the nft to the lister
+    Return,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ListingReservation {
+    pub buyer: Addr,
+    pub expires_at: Option<Timestamp>,
+    pub on_expiry: ReservationExpiry,
+}
+
+impl ListingReservation {
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+// key: (reserved buyer, nft contract address, nft token id)
+pub const NFT_RESERVED_LISTINGS: Map<(&str, &str, &str), Empty> = Map::new("nft_reserved_listings");
+
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct NftBid {
     pub bidder: Addr,
-    pub price: Decimal,
+    // in base units of the payment asset
+    pub price: Uint128,
     pub nft_contract_address: Addr,
     pub token_id: String,
+    #[serde(default)]
+    pub funding: BidFunding,
+    #[serde(default)]
+    pub payment_asset: PaymentAsset,
+}
+
+// where the bid price is taken from when the bid gets filled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BidFunding {
+    // sent together with the bid and held by the contract
+    #[default]
+    Escrow,
+    // drawn from the bidder deposit balance only when the bid is filled
+    Balance,
 }
 
 // key: (nft contract address, nft token id, bidder)
@@ -34,8 +119,330 @@ pub const NFT_BIDS: Map<(&str, &str, &str), NftBid> = Map::new("nft_bids");
 pub struct NftCollectionBid {
     pub bidder: Addr,
     pub nft_contract_address: Addr,
-    pub bids_prices: Vec<Decimal>,
+    // in base units of the payment asset
+    pub bids_prices: Vec<Uint128>,
+    #[serde(default)]
+    pub funding: BidFunding,
+    #[serde(default)]
+    pub payment_asset: PaymentAsset,
 }
 
 // key: (nft contract address, bidder)
 pub const NFT_COLLECTION_BIDS: Map<(&str, &str), NftCollectionBid> = Map::new("nft_collection_bids");
+
+// key: ((nft contract address, payment asset id), price atomics, bidder), value: number of bids of the bidder at the price
+// ranged in descending order to find the best collection bid without loading every bid of the collection
+pub const NFT_COLLECTION_BID_PRICES: Map<((&str, &str), u128, &str), u32> = Map::new("nft_collection_bid_prices");
+
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TraitFilter {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct NftTraitBid {
+    pub id: u64,
+    pub bidder: Addr,
+    pub price: Uint128,
+    pub nft_contract_address: Addr,
+    pub traits: Vec<TraitFilter>,
+}
+
+pub const NFT_TRAIT_BIDS_COUNT: Item<u64> = Item::new("nft_trait_bids_count");
+
+// key: (nft contract address, trait bid id)
+pub const NFT_TRAIT_BIDS: Map<(&str, u64), NftTraitBid> = Map::new("nft_trait_bids");
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct NftTokenSetBid {
+    pub id: u64,
+    pub bidder: Addr,
+    pub price: Uint128,
+    pub nft_contract_address: Addr,
+    // sha256 merkle root over sha256(token_id) leaves, pairs are sorted before hashing
+    pub merkle_root: Binary,
+}
+
+pub const NFT_TOKEN_SET_BIDS_COUNT: Item<u64> = Item::new("nft_token_set_bids_count");
+
+// key: (nft contract address, token set bid id)
+pub const NFT_TOKEN_SET_BIDS: Map<(&str, u64), NftTokenSetBid> = Map::new("nft_token_set_bids");
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct NftItem {
+    pub nft_contract_address: Addr,
+    pub token_id: String,
+}
+
+// how the bundle price is split between items when asking nft contracts for royalties
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleRoyaltyRule {
+    Equal,
+    // one weight per item, in the same order as the bundle items
+    Weighted { weights: Vec<u64> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct NftBundle {
+    pub id: u64,
+    pub lister: Addr,
+    pub price: Uint128,
+    pub items: Vec<NftItem>,
+    pub royalty_rule: BundleRoyaltyRule,
+}
+
+pub const NFT_BUNDLES_COUNT: Item<u64> = Item::new("nft_bundles_count");
+
+// key: bundle id
+pub const NFT_BUNDLES: Map<u64, NftBundle> = Map::new("nft_bundles");
+
+// key: (nft contract address, nft token id), value: bundle id
+pub const NFT_BUNDLED_ITEMS: Map<(&str, &str), u64> = Map::new("nft_bundled_items");
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct NftSwap {
+    pub id: u64,
+    pub maker: Addr,
+    pub offered_nfts: Vec<NftItem>,
+    // usei escrowed by the maker on top of the offered nfts
+    pub offered_amount: Uint128,
+    pub requested_nfts: Vec<NftItem>,
+    // when set only this address can accept the swap
+    pub counterparty: Option<Addr>,
+}
+
+pub const NFT_SWAPS_COUNT: Item<u64> = Item::new("nft_swaps_count");
+
+// key: swap id
+pub const NFT_SWAPS: Map<u64, NftSwap> = Map::new("nft_swaps");
+
+// key: (maker, swap id)
+pub const NFT_SWAPS_BY_MAKER: Map<(&str, u64), Empty> = Map::new("nft_swaps_by_maker");
+
+// key: (counterparty, swap id)
+pub const NFT_SWAPS_BY_TAKER: Map<(&str, u64), Empty> = Map::new("nft_swaps_by_taker");
+
+// key: (nft contract address, nft token id), value: swap id
+pub const NFT_SWAP_ITEMS: Map<(&str, &str), u64> = Map::new("nft_swap_items");
+
+
+// key: account, value: deposited usei bids with balance funding are filled from
+pub const BALANCES: Map<&str, Uint128> = Map::new("balances");
+
+
+// key: maker, value: lowest nonce a signed order of the maker can still have
+pub const MAKER_NONCES: Map<&str, u64> = Map::new("maker_nonces");
+
+// key: sha256 of the signed order sign doc, present once the order is filled or cancelled
+pub const SIGNED_ORDERS_CANCELLED_OR_FILLED: Map<&[u8], Empty> = Map::new("signed_orders_cancelled_or_filled");
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    pub admin: Addr,
+    // part of the platform fee paid to the referrer of a trade
+    #[serde(default)]
+    pub referral_fee_share: Decimal,
+    // only verified collections can be traded
+    #[serde(default)]
+    pub curation_mode: bool,
+    // seconds before an nft can be sold back to the address it was bought from, 0 disables the cooldown
+    #[serde(default)]
+    pub resale_cooldown_seconds: u64,
+    // recipient types whose proceeds are credited to claimable balances instead of sent
+    #[serde(default)]
+    pub pull_payouts: Vec<PayoutRecipientType>,
+    // gas every hook sub message can use, a hook running out of gas fails without reverting the trade
+    #[serde(default = "default_hook_gas_limit")]
+    pub hook_gas_limit: u64,
+}
+
+pub const DEFAULT_HOOK_GAS_LIMIT: u64 = 300_000;
+
+fn default_hook_gas_limit() -> u64 {
+    DEFAULT_HOOK_GAS_LIMIT
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutRecipientType {
+    Seller,
+    Royalty,
+    Referrer,
+    PlatformFee,
+}
+
+impl PayoutRecipientType {
+    pub fn id(&self) -> &str {
+        match self {
+            PayoutRecipientType::Seller => "seller",
+            PayoutRecipientType::Royalty => "royalty",
+            PayoutRecipientType::Referrer => "referrer",
+            PayoutRecipientType::PlatformFee => "platform_fee",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ClaimableBalance {
+    pub payment_asset: PaymentAsset,
+    pub amount: Uint128,
+}
+
+// key: (recipient address, payment asset id), proceeds waiting to be claimed
+pub const CLAIMABLE_BALANCES: Map<(&str, &str), ClaimableBalance> = Map::new("claimable_balances");
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+// key: hook contract address, notified after every sale, listing and cancel
+pub const HOOKS: Map<&str, Empty> = Map::new("hooks");
+
+// key: (referrer, payment asset id), value: amount earned from referral fees
+pub const REFERRAL_EARNINGS: Map<(&str, &str), Uint128> = Map::new("referral_earnings");
+
+
+// platform fee rates, the maker rested the order on the book and the taker filled it
+// both sides of a sale pay their own rate, the defaults add up to the former flat 2%
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeSchedule {
+    pub maker_fee: Decimal,
+    pub taker_fee: Decimal,
+    // sorted by min_volume, the highest tier reached by the trailing volume replaces the base rates
+    pub tiers: Vec<FeeTier>,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        FeeSchedule {
+            maker_fee: Decimal::zero(),
+            taker_fee: Decimal::percent(2),
+            tiers: vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeTier {
+    // trailing 30 day volume in usei
+    pub min_volume: Uint128,
+    pub maker_fee: Decimal,
+    pub taker_fee: Decimal,
+}
+
+pub const FEE_SCHEDULE: Item<FeeSchedule> = Item::new("fee_schedule");
+
+// key: (account, day since unix epoch), value: usei traded by the account as buyer or seller that day
+pub const TRADE_VOLUMES: Map<(&str, u64), Uint128> = Map::new("trade_volumes");
+
+// total platform fee of a sale in a partner collection, paid by the taker in place of the maker and taker rates while active
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CollectionFeeOverride {
+    pub fee: Decimal,
+    pub starts_at: Option<Timestamp>,
+    pub ends_at: Option<Timestamp>,
+}
+
+impl CollectionFeeOverride {
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        self.starts_at.is_none_or(|starts_at| now >= starts_at) && self.ends_at.is_none_or(|ends_at| now < ends_at)
+    }
+}
+
+// key: nft contract address
+pub const COLLECTION_FEE_OVERRIDES: Map<&str, CollectionFeeOverride> = Map::new("collection_fee_overrides");
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct RoyaltyRecipient {
+    pub address: Addr,
+    // part of the sale price
+    pub share: Decimal,
+}
+
+// key: nft contract address, set by the collection minter or owner, takes precedence over the nft contract royalties
+pub const ROYALTY_REGISTRY: Map<&str, Vec<RoyaltyRecipient>> = Map::new("royalty_registry");
+
+// key: cw20 contract address accepted as payment asset
+pub const CW20_ALLOWLIST: Map<&str, Empty> = Map::new("cw20_allowlist");
+
+// verification status and display metadata of a collection, set by the admin
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CollectionInfo {
+    pub verified: bool,
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub external_url: Option<String>,
+}
+
+// key: nft contract address
+pub const COLLECTIONS: Map<&str, CollectionInfo> = Map::new("collections");
+
+// trading freeze set by the collection minter or owner, e.g. until metadata is revealed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CollectionFreeze {
+    pub unfreeze_at: Option<Timestamp>,
+}
+
+impl CollectionFreeze {
+    pub fn is_frozen(&self, now: Timestamp) -> bool {
+        self.unfreeze_at.is_none_or(|unfreeze_at| now < unfreeze_at)
+    }
+}
+
+// key: nft contract address
+pub const COLLECTION_FREEZES: Map<&str, CollectionFreeze> = Map::new("collection_freezes");
+
+// key: native denom accepted as payment asset next to usei
+pub const DENOM_ALLOWLIST: Map<&str, Empty> = Map::new("denom_allowlist");
+
+
+// set by governance through sudo, nothing can be executed while paused
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+// key: address that can't execute anything, set by governance through sudo
+pub const BLOCKLIST: Map<&str, Empty> = Map::new("blocklist");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LastSale {
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub sold_at: Timestamp,
+}
+
+// key: (nft contract address, token id)
+pub const LAST_SALES: Map<(&str, &str), LastSale> = Map::new("last_sales");
+
+// key: moderator address, moderators can update the address and nft blocklists
+pub const MODERATORS: Map<&str, Empty> = Map::new("moderators");
+
+// key: address that can't execute anything, set by the admin or a moderator
+// kept apart from BLOCKLIST so an execute can't lift a block set by governance
+pub const MODERATED_BLOCKLIST: Map<&str, Empty> = Map::new("moderated_blocklist");
+
+// key: (nft contract address, token id), nfts reported stolen
+pub const BLOCKED_NFTS: Map<(&str, &str), Empty> = Map::new("blocked_nfts");
+
+
+// paginated migration that has not finished yet, only ContinueMigration can be executed meanwhile
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrationProgress {
+    // contract version the migration started from, steps of later versions run
+    pub from_version: String,
+    // index of the step to continue with
+    pub step: u32,
+    // raw key of the last migrated entry of the step
+    pub start_after: Option<Binary>,
+    pub migrated: u64,
+    // the step is done and the keys it recorded as converted are being removed
+    #[serde(default)]
+    pub clearing_migrated_keys: bool,
+}
+
+pub const MIGRATION_PROGRESS: Item<MigrationProgress> = Item::new("migration_progress");
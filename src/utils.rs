@@ -1,7 +1,13 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{coins, to_json_binary, BankMsg, Decimal, DepsMut, QueryRequest, Response, StdResult, Uint128, WasmQuery};
+use cosmwasm_std::{coins, to_json_binary, Addr, Api, BankMsg, Binary, CosmosMsg, Decimal, DepsMut, Env, QueryRequest, Response, StdResult, Storage, Uint128, WasmMsg, WasmQuery};
+use cw20::Cw20ExecuteMsg;
+use cw721::{ApprovalResponse, NftInfoResponse, OperatorResponse, OwnerOfResponse};
+use std::collections::HashSet;
 
-use crate::ContractError;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{execute::{blocklist::{ensure_address_not_blocked, ensure_nft_not_blocked}, claims::add_payout, fees::{platform_fee_rate, record_trade_volume, FeeRole}, listing::remove_nft_listing, wash_trading::{ensure_not_wash_trade, record_sale}}, state::{ListingCustody, NftItem, PaymentAsset, PayoutRecipientType, TraitFilter, CONFIG, CW20_ALLOWLIST, DENOM_ALLOWLIST, NFT_BUNDLED_ITEMS, NFT_LISTINGS, NFT_SWAP_ITEMS, PLATFORM_FEE_RECEIVER, REFERRAL_EARNINGS, ROYALTY_REGISTRY}, ContractError};
 
 #[cw_serde]
 struct Extension<T> {
@@ -87,25 +93,497 @@ pub fn query_royalty_info(deps: &DepsMut, nft_contract_address: String, token_id
 }
 
 
-pub fn add_transfer_sei_to_seller_msg_with_price_after_platform_fee(
-    seller: String,
-    price_after_platform_fee: u128,
+// standard cw721 on-chain metadata extension, only the attributes are needed for trait matching
+#[derive(Deserialize, Default)]
+pub struct Metadata {
+    #[serde(default)]
+    pub attributes: Option<Vec<Trait>>,
+}
+
+#[derive(Deserialize)]
+pub struct Trait {
+    pub trait_type: String,
+    pub value: String,
+}
+
+pub fn query_nft_metadata(deps: &DepsMut, nft_contract_address: String, token_id: String) -> StdResult<Metadata> {
+    let cw721_query_nft_info_msg = cw721::Cw721QueryMsg::NftInfo { token_id };
+
+    let wasm_query = QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: nft_contract_address,
+        msg: to_json_binary(&cw721_query_nft_info_msg)?,
+    });
+
+    let nft_info_response: NftInfoResponse<Option<Metadata>> = deps.querier.query(&wasm_query)?;
+    Ok(nft_info_response.extension.unwrap_or_default())
+}
+
+// every trait filter has to be present in the nft attributes
+pub fn metadata_matches_traits(metadata: &Metadata, traits: &[TraitFilter]) -> bool {
+    let attributes = metadata.attributes.as_deref().unwrap_or_default();
+    traits.iter().all(|trait_filter| {
+        attributes.iter().any(|attribute| attribute.trait_type == trait_filter.trait_type && attribute.value == trait_filter.value)
+    })
+}
+
+
+// leaf is sha256(token_id), every step hashes the sorted pair so proofs don't need to carry positions
+pub fn verify_merkle_proof(merkle_root: &Binary, token_id: &str, proof: &[Binary]) -> bool {
+    let leaf: [u8; 32] = Sha256::digest(token_id.as_bytes()).into();
+    let computed_root = proof.iter().try_fold(leaf, |hash, proof_element| {
+        let proof_element: [u8; 32] = proof_element.as_slice().try_into().ok()?;
+        let (first, second) = if hash <= proof_element { (hash, proof_element) } else { (proof_element, hash) };
+        let mut hasher = Sha256::new();
+        hasher.update(first);
+        hasher.update(second);
+        Some(hasher.finalize().into())
+    });
+    computed_root.is_some_and(|computed_root| computed_root.as_slice() == merkle_root.as_slice())
+}
+
+
+pub fn verify_nft_in_escrow(deps: &DepsMut, env: &Env, nft_contract_address: &Addr, token_id: String) -> Result<(), ContractError> {
+    let cw721_query_owner_msg = cw721::Cw721QueryMsg::OwnerOf {
+        token_id,
+        include_expired: Some(false),
+    };
+
+    let cw721_query = QueryRequest::Wasm(
+        WasmQuery::Smart {
+            contract_addr: nft_contract_address.to_string(),
+            msg: to_json_binary(&cw721_query_owner_msg)?
+        }
+    );
+
+    let cw721_query_response: OwnerOfResponse = deps.querier.query(&cw721_query)?;
+    if cw721_query_response.owner != env.contract.address {
+        return Err(ContractError::NftNotInEscrow {  });
+    }
+
+    Ok(())
+}
+
+// nft is either in escrow backing a listing of the seller, which is removed as the sale replaces it,
+// or it stays in the wallet of the seller, who has to own it or be an operator of the owner, and the marketplace is approved
+pub fn verify_seller_can_deliver_nft(deps: &mut DepsMut, env: &Env, nft_contract_address: &Addr, token_id: String, seller: &Addr) -> Result<(), ContractError> {
+    let owner = query_nft_owner(deps, nft_contract_address, token_id.clone())?;
+    if owner == env.contract.address {
+        let nft_listing = NFT_LISTINGS.may_load(deps.storage, (nft_contract_address.as_str(), token_id.as_str()))?
+            .filter(|nft_listing| nft_listing.custody == ListingCustody::Escrow && nft_listing.lister == *seller)
+            .ok_or(ContractError::NftNotOwnedBySeller {  })?;
+        remove_nft_listing(deps.storage, &nft_listing);
+        return Ok(());
+    }
+
+    if owner != seller.as_str() {
+        let cw721_query_operator_msg = cw721::Cw721QueryMsg::Operator {
+            owner: owner.clone(),
+            operator: seller.to_string(),
+            include_expired: Some(false),
+        };
+        let operator_response: StdResult<OperatorResponse> = deps.querier.query_wasm_smart(nft_contract_address, &cw721_query_operator_msg);
+        if operator_response.is_err() {
+            return Err(ContractError::NftNotOwnedBySeller {  });
+        }
+    }
+
+    if !is_marketplace_approved(deps, env, nft_contract_address, token_id, owner) {
+        return Err(ContractError::NftNotApproved {  });
+    }
+
+    Ok(())
+}
+
+// items are (nft contract address, token id) pairs
+pub fn validate_nft_items(api: &dyn Api, items: Vec<(String, String)>) -> Result<Vec<NftItem>, ContractError> {
+    items
+        .into_iter()
+        .map(|(nft_contract_address, token_id)| {
+            let nft_contract_address = api.addr_validate(nft_contract_address.as_str())
+                .map_err(|_e| ContractError::InvalidNftContractAddress {  })?;
+            Ok(NftItem { nft_contract_address, token_id })
+        })
+        .collect()
+}
+
+pub fn has_duplicate_nft_items(items: &[NftItem]) -> bool {
+    let unique_items = items
+        .iter()
+        .map(|item| (item.nft_contract_address.as_str(), item.token_id.as_str()))
+        .collect::<HashSet<_>>();
+    unique_items.len() != items.len()
+}
+
+// escrowed nfts that back a listing, bundle or swap can't be used again
+pub fn ensure_nft_not_in_use(storage: &dyn Storage, item: &NftItem) -> Result<(), ContractError> {
+    let key = (item.nft_contract_address.as_str(), item.token_id.as_str());
+    if NFT_LISTINGS.has(storage, key) {
+        return Err(ContractError::ListingAlreadyExists {  });
+    }
+    if NFT_BUNDLED_ITEMS.has(storage, key) {
+        return Err(ContractError::NftAlreadyInBundle {  });
+    }
+    if NFT_SWAP_ITEMS.has(storage, key) {
+        return Err(ContractError::NftAlreadyInSwap {  });
+    }
+    Ok(())
+}
+
+pub fn nft_items_separated_by_comma(items: &[NftItem]) -> String {
+    items
+        .iter()
+        .map(|item| format!("{}:{}", item.nft_contract_address, item.token_id))
+        .collect::<Vec<String>>().join(",")
+}
+
+#[cw_serde]
+enum Cw721CreatorQueryMsg {
+    Minter {},
+    Ownership {},
+}
+
+// only the fields needed, ownership responses also carry the pending owner
+#[derive(Deserialize)]
+struct MinterResponse {
+    minter: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OwnershipResponse {
+    owner: Option<String>,
+}
+
+// collection minter or owner, either of the queries may be missing depending on the cw721 version
+pub fn is_collection_creator(deps: &DepsMut, nft_contract_address: &Addr, account: &Addr) -> bool {
+    let minter_response: StdResult<MinterResponse> = deps.querier.query_wasm_smart(nft_contract_address, &Cw721CreatorQueryMsg::Minter {});
+    if minter_response.is_ok_and(|minter_response| minter_response.minter.as_deref() == Some(account.as_str())) {
+        return true;
+    }
+
+    let ownership_response: StdResult<OwnershipResponse> = deps.querier.query_wasm_smart(nft_contract_address, &Cw721CreatorQueryMsg::Ownership {});
+    ownership_response.is_ok_and(|ownership_response| ownership_response.owner.as_deref() == Some(account.as_str()))
+}
+
+pub fn query_nft_owner(deps: &DepsMut, nft_contract_address: &Addr, token_id: String) -> StdResult<String> {
+    let cw721_query_owner_msg = cw721::Cw721QueryMsg::OwnerOf {
+        token_id,
+        include_expired: Some(false),
+    };
+    let cw721_query_response: OwnerOfResponse = deps.querier.query_wasm_smart(nft_contract_address, &cw721_query_owner_msg)?;
+    Ok(cw721_query_response.owner)
+}
+
+// marketplace can move the nft either through a token approval or as an operator of the owner
+pub fn is_marketplace_approved(deps: &DepsMut, env: &Env, nft_contract_address: &Addr, token_id: String, owner: String) -> bool {
+    let cw721_query_approval_msg = cw721::Cw721QueryMsg::Approval {
+        token_id,
+        spender: env.contract.address.to_string(),
+        include_expired: Some(false),
+    };
+    let approval_response: StdResult<ApprovalResponse> = deps.querier.query_wasm_smart(nft_contract_address, &cw721_query_approval_msg);
+    if approval_response.is_ok() {
+        return true;
+    }
+
+    let cw721_query_operator_msg = cw721::Cw721QueryMsg::Operator {
+        owner,
+        operator: env.contract.address.to_string(),
+        include_expired: Some(false),
+    };
+    let operator_response: StdResult<OperatorResponse> = deps.querier.query_wasm_smart(nft_contract_address, &cw721_query_operator_msg);
+    operator_response.is_ok()
+}
+
+pub fn transfer_nft_msg(nft_contract_address: &Addr, recipient: String, token_id: String) -> StdResult<WasmMsg> {
+    let cw721_transfer_nft_msg = cw721::Cw721ExecuteMsg::TransferNft {
+        recipient,
+        token_id,
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: nft_contract_address.to_string(),
+        msg: to_json_binary(&cw721_transfer_nft_msg)?,
+        funds: vec![]
+    })
+}
+
+
+// seller and buyer each pay the platform fee at their own maker or taker rate
+// when the price is already escrowed both fees and royalties come out of it,
+// with buyer_sent_amount the buyer pays its fee and royalties on top of the price
+#[allow(clippy::too_many_arguments)]
+pub fn add_sale_payout_msgs(
+    deps: &mut DepsMut,
+    env: &Env,
+    nft_contract_address: &Addr,
+    token_id: String,
+    price: Uint128,
+    seller: &Addr,
+    seller_fee_role: FeeRole,
+    buyer: &Addr,
+    payment_asset: &PaymentAsset,
+    buyer_sent_amount: Option<Uint128>,
+    referrer: Option<&Addr>,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let (response, total_amount) = add_sale_item_payout_msgs(
+        deps,
+        env,
+        nft_contract_address,
+        token_id,
+        price,
+        seller,
+        seller_fee_role,
+        buyer,
+        payment_asset,
+        buyer_sent_amount.is_some(),
+        referrer,
+        response,
+    )?;
+
+    match buyer_sent_amount {
+        Some(sent_amount) => add_buyer_payment_msgs(payment_asset, buyer, sent_amount, total_amount, response),
+        None => Ok(response),
+    }
+}
+
+// pays out the sale of one nft and returns the amount the buyer owes for it,
+// the price alone when it is already escrowed, otherwise the price with the buyer fee and royalties
+#[allow(clippy::too_many_arguments)]
+pub fn add_sale_item_payout_msgs(
+    deps: &mut DepsMut,
+    env: &Env,
+    nft_contract_address: &Addr,
+    token_id: String,
+    price: Uint128,
+    seller: &Addr,
+    seller_fee_role: FeeRole,
+    buyer: &Addr,
+    payment_asset: &PaymentAsset,
+    buyer_pays_on_top: bool,
+    referrer: Option<&Addr>,
+    response: Response,
+) -> Result<(Response, Uint128), ContractError> {
+    // every sale path pays out here, so blocked nfts and counterparties are rejected once for all of them
+    ensure_nft_not_blocked(deps.storage, nft_contract_address, token_id.as_str())?;
+    ensure_address_not_blocked(deps.storage, seller)?;
+    ensure_address_not_blocked(deps.storage, buyer)?;
+    ensure_not_wash_trade(deps.storage, env, nft_contract_address, token_id.as_str(), seller, buyer)?;
+    record_sale(deps.storage, env, nft_contract_address, token_id.as_str(), seller, buyer)?;
+
+    // rates come from the volume tiers reached before this sale
+    let buyer_fee_role = seller_fee_role.counterparty();
+    let seller_fee = price * platform_fee_rate(deps.storage, env, nft_contract_address, seller.as_str(), seller_fee_role)?;
+    let buyer_fee = price * platform_fee_rate(deps.storage, env, nft_contract_address, buyer.as_str(), buyer_fee_role)?;
+    let platform_fee = seller_fee.checked_add(buyer_fee)
+        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+
+    // volume tiers are measured in usei
+    if *payment_asset == PaymentAsset::default() {
+        record_trade_volume(deps.storage, env, seller.as_str(), price)?;
+        record_trade_volume(deps.storage, env, buyer.as_str(), price)?;
+    }
+
+    let mut response = add_platform_fee_msgs(deps.storage, payment_asset, platform_fee, referrer, response)?;
+
+    // pay royalties
+    let royalty_payments = query_royalty_payments(deps, nft_contract_address, token_id, price)?;
+    let mut royalties = Uint128::zero();
+    for royalty_payment in royalty_payments {
+        royalties = royalties.checked_add(royalty_payment.royalty_amount)
+            .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+        response = add_payout(deps.storage, PayoutRecipientType::Royalty, payment_asset, royalty_payment.address, royalty_payment.royalty_amount, response)?;
+    }
+
+    let response = response
+        .add_attribute("seller_fee", seller_fee.to_string())
+        .add_attribute("buyer_fee", buyer_fee.to_string());
+
+    if buyer_pays_on_top {
+        let total_amount = price.checked_add(buyer_fee)
+            .and_then(|total_amount| total_amount.checked_add(royalties))
+            .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+        let price_after_seller_fee = price.checked_sub(seller_fee)
+            .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+        let response = add_payout(deps.storage, PayoutRecipientType::Seller, payment_asset, seller.to_string(), price_after_seller_fee, response)?;
+        Ok((response, total_amount))
+    } else {
+        let price_after_platform_fee_and_royalties = price.checked_sub(platform_fee)
+            .and_then(|price_after_platform_fee| price_after_platform_fee.checked_sub(royalties))
+            .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+        let response = add_payout(deps.storage, PayoutRecipientType::Seller, payment_asset, seller.to_string(), price_after_platform_fee_and_royalties, response)?;
+        Ok((response, price))
+    }
+}
+
+// amount due from a buyer paying on top of the price: the price, the buyer fee at the counterparty role of the seller and royalties
+pub fn sale_buyer_total_amount(
+    deps: &DepsMut,
+    env: &Env,
+    nft_contract_address: &Addr,
+    token_id: String,
+    price: Uint128,
+    buyer: &Addr,
+    seller_fee_role: FeeRole,
+) -> Result<Uint128, ContractError> {
+    let buyer_fee = price * platform_fee_rate(deps.storage, env, nft_contract_address, buyer.as_str(), seller_fee_role.counterparty())?;
+    let royalties = query_royalty_payments(deps, nft_contract_address, token_id, price)?
+        .iter()
+        .try_fold(Uint128::zero(), |royalties, royalty_payment| royalties.checked_add(royalty_payment.royalty_amount))
+        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+    price.checked_add(buyer_fee)
+        .and_then(|total_amount| total_amount.checked_add(royalties))
+        .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))
+}
+
+// the buyer has to send at least the amount due, anything above it goes back to the buyer
+pub fn add_buyer_payment_msgs(
+    payment_asset: &PaymentAsset,
+    buyer: &Addr,
+    sent_amount: Uint128,
+    total_amount: Uint128,
     response: Response,
-) -> Response {
-    let transfer_sei_msg = BankMsg::Send {
-        to_address: seller,
-        amount: coins(price_after_platform_fee, "usei")
+) -> Result<Response, ContractError> {
+    if sent_amount < total_amount {
+        return Err(ContractError::InsufficientFundsSent {  });
+    }
+
+    let mut response = response.add_attribute("total_amount", total_amount.to_string());
+    let surplus = sent_amount - total_amount;
+    if !surplus.is_zero() {
+        let refund_msg = payment_msg(payment_asset, buyer.to_string(), surplus)?;
+        response = response.add_message(refund_msg);
+    }
+    Ok(response)
+}
+
+// sends the platform fee to the fee receiver, the configured referral share of it goes to the referrer
+pub fn add_platform_fee_msgs(
+    storage: &mut dyn Storage,
+    payment_asset: &PaymentAsset,
+    platform_fee: Uint128,
+    referrer: Option<&Addr>,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let mut response = response;
+    let mut platform_fee = platform_fee;
+
+    if let Some(referrer) = referrer {
+        let referral_fee_share = CONFIG.may_load(storage)?.map_or(Decimal::zero(), |config| config.referral_fee_share);
+        let referral_fee = platform_fee * referral_fee_share;
+        if !referral_fee.is_zero() {
+            REFERRAL_EARNINGS.update(storage, (referrer.as_str(), payment_asset.id()), |earnings| -> StdResult<Uint128> {
+                Ok(earnings.unwrap_or_default().checked_add(referral_fee)?)
+            })?;
+            platform_fee = platform_fee.checked_sub(referral_fee)
+                .map_err(|e| ContractError::Std(cosmwasm_std::StdError::Overflow { source: e }))?;
+            response = add_payout(storage, PayoutRecipientType::Referrer, payment_asset, referrer.to_string(), referral_fee, response)?;
+        }
+        response = response
+            .add_attribute("referrer", referrer.to_string())
+            .add_attribute("referral_fee", referral_fee.to_string());
+    }
+
+    add_payout(storage, PayoutRecipientType::PlatformFee, payment_asset, PLATFORM_FEE_RECEIVER.to_string(), platform_fee, response)
+}
+
+// bank send for native denoms, cw20 transfer from the contract balance otherwise
+pub fn payment_msg(payment_asset: &PaymentAsset, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match payment_asset {
+        PaymentAsset::Native { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount: coins(amount.u128(), denom)
+        }),
+        PaymentAsset::Cw20 { contract_address } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+            funds: vec![]
+        }),
     };
-    response.clone().add_message(transfer_sei_msg)
-}
-
-// need to remove extra zeros because
-// 1 sei = 1_000_000 usei
-// 1 sei = 1_000_000_000_000_000_000 atomics in Decimal
-// so removing extra 1_000_000_000_000
-pub fn parse_decimal(decimal: Decimal) -> Result<Uint128, ContractError> {
-    decimal
-        .atomics()
-        .checked_div(Uint128::new(1_000_000_000_000))
-        .map_err(|_e| ContractError::ParseDecimal {  })
+    Ok(msg)
+}
+
+// referrer can't be the account trading
+pub fn validate_referrer(api: &dyn Api, referrer: Option<String>, sender: &Addr) -> Result<Option<Addr>, ContractError> {
+    let referrer = referrer
+        .map(|referrer| api.addr_validate(referrer.as_str()))
+        .transpose()
+        .map_err(|_e| ContractError::InvalidReferrer {  })?;
+    if referrer.as_ref() == Some(sender) {
+        return Err(ContractError::InvalidReferrer {  });
+    }
+    Ok(referrer)
+}
+
+// only allowlisted cw20s can be used as payment asset
+pub fn validate_cw20_payment_asset(storage: &dyn Storage, api: &dyn Api, cw20_contract_address: String) -> Result<PaymentAsset, ContractError> {
+    let contract_address = api.addr_validate(cw20_contract_address.as_str())
+        .map_err(|_e| ContractError::InvalidCw20ContractAddress {  })?;
+    if !CW20_ALLOWLIST.has(storage, contract_address.as_str()) {
+        return Err(ContractError::Cw20NotAllowed {  });
+    }
+    Ok(PaymentAsset::Cw20 { contract_address })
+}
+
+// usei unless an allowlisted native denom is given
+pub fn validate_native_payment_asset(storage: &dyn Storage, denom: Option<String>) -> Result<PaymentAsset, ContractError> {
+    match denom {
+        Some(denom) if denom != PaymentAsset::default().id() => {
+            if !DENOM_ALLOWLIST.has(storage, denom.as_str()) {
+                return Err(ContractError::DenomNotAllowed {  });
+            }
+            Ok(PaymentAsset::Native { denom })
+        },
+        _ => Ok(PaymentAsset::default()),
+    }
+}
+
+// royalty registry recipients when the collection is registered, otherwise the nft contract royalty if it supports royalties
+pub fn query_royalty_payments(
+    deps: &DepsMut,
+    nft_contract_address: &Addr,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<Vec<RoyaltyInfoResponse>> {
+    if let Some(royalty_recipients) = ROYALTY_REGISTRY.may_load(deps.storage, nft_contract_address.as_str())? {
+        let royalty_payments = royalty_recipients
+            .into_iter()
+            .map(|royalty_recipient| RoyaltyInfoResponse {
+                address: royalty_recipient.address.to_string(),
+                royalty_amount: sale_price * royalty_recipient.share,
+            })
+            .filter(|royalty_payment| !royalty_payment.royalty_amount.is_zero())
+            .collect();
+        return Ok(royalty_payments);
+    }
+
+    Ok(query_royalty_payment(deps, nft_contract_address, token_id, sale_price)?.into_iter().collect())
+}
+
+// returns None when the nft contract does not support royalties or there is nothing to pay
+fn query_royalty_payment(
+    deps: &DepsMut,
+    nft_contract_address: &Addr,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<Option<RoyaltyInfoResponse>> {
+    let check_royalties_response_result = query_check_royalties(deps, nft_contract_address.to_string());
+    match check_royalties_response_result {
+        Ok(check_royalties_response) => {
+            if !check_royalties_response.royalty_payments {
+                return Ok(None);
+            }
+            let royalty_info_response = query_royalty_info(
+                deps,
+                nft_contract_address.to_string(),
+                token_id,
+                sale_price,
+            )?;
+            if royalty_info_response.address.is_empty() || royalty_info_response.royalty_amount.is_zero() {
+                return Ok(None);
+            }
+            Ok(Some(royalty_info_response))
+        },
+        // if there is an error that means the nft contract does not support royalties
+        Err(_e) => Ok(None)
+    }
 }
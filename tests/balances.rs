@@ -0,0 +1,87 @@
+mod common;
+
+use cosmwasm_std::Uint128;
+use tradesei::{msg::{ExecuteMsg, GetBalanceResponse, QueryMsg}, ContractError};
+
+use common::{bank_sends, nft_transfers, usei, Suite, NFT};
+
+fn balance(suite: &Suite, account: &str) -> Uint128 {
+    let response: GetBalanceResponse = suite.query(QueryMsg::GetBalance { account: account.to_string() });
+    response.balance
+}
+
+fn balance_bid(suite: &mut Suite, bidder: &str, token_id: &str, price: u128) {
+    suite.execute(bidder, &[], ExecuteMsg::Bid {
+        price: Uint128::new(price),
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        use_balance: Some(true),
+        denom: None,
+    }).unwrap();
+}
+
+fn sell_to_bid(suite: &mut Suite, seller: &str, token_id: &str, bidder: &str) -> Result<cosmwasm_std::Response, ContractError> {
+    suite.escrow(token_id);
+    suite.list(seller, token_id, 5_000_000).unwrap();
+    suite.execute(seller, &[], ExecuteMsg::SellToBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        bidder: bidder.to_string(),
+        referrer: None,
+    })
+}
+
+#[test]
+fn balance_funded_bids_draw_from_the_deposit_when_filled() {
+    let mut suite = Suite::new();
+    suite.execute("bidder", &usei(1_500_000), ExecuteMsg::Deposit {}).unwrap();
+    // both bids are backed by the same deposit
+    balance_bid(&mut suite, "bidder", "1", 1_000_000);
+    balance_bid(&mut suite, "bidder", "2", 1_000_000);
+    assert_eq!(balance(&suite, "bidder"), Uint128::new(1_500_000));
+
+    let response = sell_to_bid(&mut suite, "seller", "1", "bidder").unwrap();
+    assert_eq!(nft_transfers(&response), vec![("bidder".to_string(), "1".to_string())]);
+    assert!(bank_sends(&response).contains(&("seller".to_string(), 980_000)));
+    assert_eq!(balance(&suite, "bidder"), Uint128::new(500_000));
+
+    // the second bid is over-committed now and can't be filled
+    let err = sell_to_bid(&mut suite, "seller", "2", "bidder").unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientBalance {  }));
+    assert_eq!(balance(&suite, "bidder"), Uint128::new(500_000));
+}
+
+#[test]
+fn withdraw_is_limited_to_the_balance() {
+    let mut suite = Suite::new();
+    suite.execute("account", &usei(1_000_000), ExecuteMsg::Deposit {}).unwrap();
+
+    let err = suite.execute("account", &[], ExecuteMsg::Withdraw { amount: Uint128::new(1_000_001) }).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientBalance {  }));
+
+    let response = suite.execute("account", &[], ExecuteMsg::Withdraw { amount: Uint128::new(1_000_000) }).unwrap();
+    assert_eq!(bank_sends(&response), vec![("account".to_string(), 1_000_000)]);
+    assert_eq!(balance(&suite, "account"), Uint128::zero());
+}
+
+#[test]
+fn deposit_without_funds_is_rejected() {
+    let mut suite = Suite::new();
+    let err = suite.execute("account", &[], ExecuteMsg::Deposit {}).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+}
+
+#[test]
+fn funds_sent_with_a_balance_bid_update_top_up_the_deposit() {
+    let mut suite = Suite::new();
+    suite.execute("bidder", &usei(1_000_000), ExecuteMsg::Deposit {}).unwrap();
+    balance_bid(&mut suite, "bidder", "1", 1_000_000);
+
+    let response = suite.execute("bidder", &usei(500_000), ExecuteMsg::UpdateBid {
+        new_price: Uint128::new(1_500_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+    }).unwrap();
+    assert!(bank_sends(&response).is_empty());
+    assert_eq!(balance(&suite, "bidder"), Uint128::new(1_500_000));
+}
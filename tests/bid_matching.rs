@@ -0,0 +1,94 @@
+mod common;
+
+use cosmwasm_std::{Response, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetNftBidResponse, QueryMsg}, ContractError};
+
+use common::{attribute, bank_sends, nft_transfers, usei, Suite, ADMIN, NFT};
+
+fn bid(suite: &mut Suite, bidder: &str, token_id: &str, price: u128) -> Result<Response, ContractError> {
+    suite.execute(bidder, &usei(price), ExecuteMsg::Bid {
+        price: Uint128::new(price),
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        use_balance: None,
+        denom: None,
+    })
+}
+
+#[test]
+fn bid_above_the_listing_price_settles_at_the_listing_price() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let response = bid(&mut suite, "bidder", "1", 1_200_000).unwrap();
+    assert_eq!(attribute(&response, "matched_listing_price"), Some("1000000"));
+    assert_eq!(nft_transfers(&response), vec![("bidder".to_string(), "1".to_string())]);
+    let sends = bank_sends(&response);
+    // the bidder pays the taker fee like with BuyListing and gets the rest back, the lister only pays the maker fee
+    assert_eq!(attribute(&response, "total_amount"), Some("1020000"));
+    assert!(sends.contains(&("bidder".to_string(), 180_000)));
+    assert!(sends.contains(&("lister".to_string(), 1_000_000)));
+
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::NftListingNotFound {  }));
+}
+
+#[test]
+fn bid_below_the_amount_due_stays_until_raised_to_it() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let response = bid(&mut suite, "bidder", "1", 900_000).unwrap();
+    assert_eq!(attribute(&response, "matched_listing_price"), None);
+    let nft_bid: GetNftBidResponse = suite.query(QueryMsg::GetNftBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        bidder: "bidder".to_string(),
+    });
+    assert_eq!(nft_bid.nft_bid.price, Uint128::new(900_000));
+
+    // the listing price alone doesn't cover the taker fee
+    let response = suite.execute("bidder", &usei(100_000), ExecuteMsg::UpdateBid {
+        new_price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+    }).unwrap();
+    assert_eq!(attribute(&response, "matched_listing_price"), None);
+
+    let response = suite.execute("bidder", &usei(20_000), ExecuteMsg::UpdateBid {
+        new_price: Uint128::new(1_020_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+    }).unwrap();
+    assert_eq!(attribute(&response, "matched_listing_price"), Some("1000000"));
+    assert_eq!(nft_transfers(&response), vec![("bidder".to_string(), "1".to_string())]);
+}
+
+#[test]
+fn lister_bid_does_not_match_its_own_listing() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let response = bid(&mut suite, "lister", "1", 1_000_000).unwrap();
+    assert_eq!(attribute(&response, "matched_listing_price"), None);
+}
+
+#[test]
+fn bid_stays_on_the_book_when_the_lister_is_blocked() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateAddressBlocklist { add: vec!["lister".to_string()], remove: vec![] }).unwrap();
+
+    let response = bid(&mut suite, "bidder", "1", 1_020_000).unwrap();
+    assert_eq!(attribute(&response, "matched_listing_price"), None);
+    let nft_bid: GetNftBidResponse = suite.query(QueryMsg::GetNftBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        bidder: "bidder".to_string(),
+    });
+    assert_eq!(nft_bid.nft_bid.price, Uint128::new(1_020_000));
+}
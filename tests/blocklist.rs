@@ -0,0 +1,128 @@
+mod common;
+
+use cosmwasm_std::{CosmosMsg, WasmMsg};
+use tradesei::{msg::{ExecuteMsg, GetPaginatedBlockedAddressesResponse, QueryMsg, SudoMsg}, ContractError};
+
+use common::{bank_sends, nft_transfers, usei, Suite, ADMIN, NFT};
+
+fn add_moderator(suite: &mut Suite, moderator: &str) {
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateModerators { add: vec![moderator.to_string()], remove: vec![] }).unwrap();
+}
+
+fn block_nft(suite: &mut Suite, sender: &str, token_id: &str) -> Result<cosmwasm_std::Response, ContractError> {
+    suite.execute(sender, &[], ExecuteMsg::UpdateNftBlocklist { add: vec![(NFT.to_string(), token_id.to_string())], remove: vec![] })
+}
+
+fn block(suite: &mut Suite, address: &str) {
+    suite.sudo(SudoMsg::UpdateBlocklist { add: vec![address.to_string()], remove: vec![] }).unwrap();
+}
+
+#[test]
+fn blocked_address_can_cancel_and_withdraw_but_not_trade() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+    suite.execute("lister", &usei(500_000), ExecuteMsg::Deposit {}).unwrap();
+
+    block(&mut suite, "lister");
+    suite.escrow("2");
+    let err = suite.list("lister", "2", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::AddressBlocked {  }));
+
+    let response = suite.execute("lister", &[], ExecuteMsg::CancelListing {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+    }).unwrap();
+    assert_eq!(nft_transfers(&response), vec![("lister".to_string(), "1".to_string())]);
+
+    let response = suite.execute("lister", &[], ExecuteMsg::Withdraw { amount: 500_000u128.into() }).unwrap();
+    assert_eq!(bank_sends(&response), vec![("lister".to_string(), 500_000)]);
+}
+
+#[test]
+fn pause_stops_cancels_of_blocked_addresses_too() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+    block(&mut suite, "lister");
+    suite.sudo(SudoMsg::Pause {}).unwrap();
+
+    let err = suite.execute("lister", &[], ExecuteMsg::CancelListing {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::Paused {  }));
+}
+
+#[test]
+fn blocked_nft_is_held_until_resolved_to_the_recipient() {
+    let mut suite = Suite::new();
+    add_moderator(&mut suite, "moderator");
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    block_nft(&mut suite, "moderator", "1").unwrap();
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::NftBlocked {  }));
+
+    let response = suite.execute(ADMIN, &[], ExecuteMsg::ResolveBlockedNft {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        recipient: "victim".to_string(),
+    }).unwrap();
+    assert!(response.messages.iter().any(|sub_msg| matches!(&sub_msg.msg, CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == NFT)));
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::NftListingNotFound {  }));
+}
+
+#[test]
+fn listing_of_a_blocked_lister_cant_be_bought() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateAddressBlocklist { add: vec!["lister".to_string()], remove: vec![] }).unwrap();
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::AddressBlocked {  }));
+}
+
+#[test]
+fn only_admin_or_moderators_update_blocklists() {
+    let mut suite = Suite::new();
+
+    let err = block_nft(&mut suite, "user", "1").unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+}
+
+#[test]
+fn moderators_cant_lift_a_governance_block() {
+    let mut suite = Suite::new();
+    add_moderator(&mut suite, "moderator");
+    block(&mut suite, "lister");
+
+    suite.execute("moderator", &[], ExecuteMsg::UpdateAddressBlocklist { add: vec![], remove: vec!["lister".to_string()] }).unwrap();
+    suite.escrow("1");
+    let err = suite.list("lister", "1", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::AddressBlocked {  }));
+
+    suite.sudo(SudoMsg::UpdateBlocklist { add: vec![], remove: vec!["lister".to_string()] }).unwrap();
+    suite.list("lister", "1", 1_000_000).unwrap();
+}
+
+#[test]
+fn moderators_block_and_unblock_addresses_apart_from_governance() {
+    let mut suite = Suite::new();
+    add_moderator(&mut suite, "moderator");
+    suite.execute("moderator", &[], ExecuteMsg::UpdateAddressBlocklist { add: vec!["lister".to_string()], remove: vec![] }).unwrap();
+
+    suite.escrow("1");
+    let err = suite.list("lister", "1", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::AddressBlocked {  }));
+    let response: GetPaginatedBlockedAddressesResponse = suite.query(QueryMsg::GetPaginatedModeratedBlockedAddresses { start_after: None, limit: None });
+    assert_eq!(response.addresses, vec!["lister".to_string()]);
+    let response: GetPaginatedBlockedAddressesResponse = suite.query(QueryMsg::GetPaginatedBlockedAddresses { start_after: None, limit: None });
+    assert!(response.addresses.is_empty());
+
+    suite.execute("moderator", &[], ExecuteMsg::UpdateAddressBlocklist { add: vec![], remove: vec!["lister".to_string()] }).unwrap();
+    suite.list("lister", "1", 1_000_000).unwrap();
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use cosmwasm_std::{Decimal, Response, Uint128};
+use tradesei::{msg::ExecuteMsg, state::BundleRoyaltyRule, ContractError};
+
+use common::{attribute, bank_sends, nft_transfers, usei, Suite, NFT};
+
+fn items(token_ids: &[&str]) -> Vec<(String, String)> {
+    token_ids.iter().map(|token_id| (NFT.to_string(), token_id.to_string())).collect()
+}
+
+fn list_bundle(suite: &mut Suite, token_ids: &[&str], royalty_rule: Option<BundleRoyaltyRule>) -> Result<Response, ContractError> {
+    for token_id in token_ids {
+        suite.escrow(token_id);
+    }
+    suite.execute("lister", &[], ExecuteMsg::ListBundle {
+        items: items(token_ids),
+        price: Uint128::new(1_000_000),
+        royalty_rule,
+    })
+}
+
+#[test]
+fn bundle_is_sold_as_one_lot_with_weighted_royalties() {
+    let mut suite = Suite::new();
+    suite.set_royalty("artist", Decimal::percent(10));
+    list_bundle(&mut suite, &["1", "2"], Some(BundleRoyaltyRule::Weighted { weights: vec![3, 1] })).unwrap();
+
+    // price, 2% platform fee and 10% royalties
+    let err = suite.execute("buyer", &usei(1_119_999), ExecuteMsg::BuyBundle { bundle_id: 1, referrer: None }).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+
+    let response = suite.execute("buyer", &usei(1_120_000), ExecuteMsg::BuyBundle { bundle_id: 1, referrer: None }).unwrap();
+    assert_eq!(attribute(&response, "total_amount"), Some("1120000"));
+    assert_eq!(nft_transfers(&response), vec![
+        ("buyer".to_string(), "1".to_string()),
+        ("buyer".to_string(), "2".to_string()),
+    ]);
+    let sends = bank_sends(&response);
+    // every item is paid out for its share of the price
+    assert!(sends.contains(&("lister".to_string(), 750_000)));
+    assert!(sends.contains(&("lister".to_string(), 250_000)));
+    assert!(sends.contains(&("artist".to_string(), 75_000)));
+    assert!(sends.contains(&("artist".to_string(), 25_000)));
+}
+
+#[test]
+fn bundle_surplus_is_refunded_to_the_buyer() {
+    let mut suite = Suite::new();
+    list_bundle(&mut suite, &["1", "2", "3"], None).unwrap();
+
+    let response = suite.execute("buyer", &usei(1_100_000), ExecuteMsg::BuyBundle { bundle_id: 1, referrer: None }).unwrap();
+    // the taker fee is taken on every item's share and rounded down per item
+    assert_eq!(attribute(&response, "total_amount"), Some("1019998"));
+    let sends = bank_sends(&response);
+    assert!(sends.contains(&("buyer".to_string(), 80_002)));
+    // the last item takes the rounding remainder of the equal split
+    let lister_amount: u128 = sends.iter().filter(|(recipient, _)| recipient == "lister").map(|(_, amount)| amount).sum();
+    assert_eq!(lister_amount, 1_000_000);
+}
+
+#[test]
+fn bundle_with_duplicate_items_is_rejected() {
+    let mut suite = Suite::new();
+    let err = list_bundle(&mut suite, &["1", "1"], None).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidBundleItems {  }));
+}
+
+#[test]
+fn bundled_nft_cant_be_listed_again() {
+    let mut suite = Suite::new();
+    list_bundle(&mut suite, &["1", "2"], None).unwrap();
+
+    let err = suite.list("lister", "1", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::NftAlreadyInBundle {  }));
+}
+
+#[test]
+fn only_the_lister_can_cancel_a_bundle() {
+    let mut suite = Suite::new();
+    list_bundle(&mut suite, &["1", "2"], None).unwrap();
+
+    let err = suite.execute("buyer", &[], ExecuteMsg::CancelBundle { bundle_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+
+    let response = suite.execute("lister", &[], ExecuteMsg::CancelBundle { bundle_id: 1 }).unwrap();
+    assert_eq!(nft_transfers(&response), vec![
+        ("lister".to_string(), "1".to_string()),
+        ("lister".to_string(), "2".to_string()),
+    ]);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use cosmwasm_std::{Decimal, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetClaimableBalancesResponse, QueryMsg}, state::{PaymentAsset, PayoutRecipientType}, ContractError};
+
+use common::{bank_sends, usei, Suite, ADMIN, NFT};
+
+fn claimable(suite: &Suite, recipient: &str) -> Vec<(PaymentAsset, Uint128)> {
+    let response: GetClaimableBalancesResponse = suite.query(QueryMsg::GetClaimableBalances { recipient: recipient.to_string() });
+    response.claimable_balances.into_iter().map(|claimable_balance| (claimable_balance.payment_asset, claimable_balance.amount)).collect()
+}
+
+#[test]
+fn pulled_seller_proceeds_are_claimed_later() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdatePayoutMode { recipient_type: PayoutRecipientType::Seller, pull: true }).unwrap();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let response = suite.buy_listing("buyer", "1", 1_020_000).unwrap();
+    assert!(!bank_sends(&response).iter().any(|(recipient, _)| recipient == "lister"));
+    assert_eq!(claimable(&suite, "lister"), vec![(PaymentAsset::default(), Uint128::new(1_000_000))]);
+
+    let response = suite.execute("lister", &[], ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(bank_sends(&response), vec![("lister".to_string(), 1_000_000)]);
+    assert!(claimable(&suite, "lister").is_empty());
+
+    let err = suite.execute("lister", &[], ExecuteMsg::Claim {}).unwrap_err();
+    assert!(matches!(err, ContractError::NothingToClaim {  }));
+}
+
+#[test]
+fn proceeds_are_pushed_by_default() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let response = suite.buy_listing("buyer", "1", 1_020_000).unwrap();
+    assert!(bank_sends(&response).contains(&("lister".to_string(), 1_000_000)));
+    assert!(claimable(&suite, "lister").is_empty());
+}
+
+#[test]
+fn only_admin_can_change_the_payout_mode() {
+    let mut suite = Suite::new();
+    let err = suite.execute("lister", &[], ExecuteMsg::UpdatePayoutMode { recipient_type: PayoutRecipientType::Seller, pull: true }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+}
+
+#[test]
+fn blocked_royalty_recipient_can_still_claim_pulled_royalties() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdatePayoutMode { recipient_type: PayoutRecipientType::Royalty, pull: true }).unwrap();
+    suite.set_royalty("artist", Decimal::percent(5));
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateAddressBlocklist { add: vec!["artist".to_string()], remove: vec![] }).unwrap();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    // the sale settles, the royalty waits in the claimable balance of the blocked recipient
+    let response = suite.buy_listing("buyer", "1", 1_070_000).unwrap();
+    assert!(!bank_sends(&response).iter().any(|(recipient, _)| recipient == "artist"));
+    assert_eq!(claimable(&suite, "artist"), vec![(PaymentAsset::default(), Uint128::new(50_000))]);
+
+    let response = suite.execute("artist", &[], ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(bank_sends(&response), vec![("artist".to_string(), 50_000)]);
+}
+
+#[test]
+fn pulled_swap_taker_is_credited_the_offered_amount() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdatePayoutMode { recipient_type: PayoutRecipientType::Seller, pull: true }).unwrap();
+    suite.escrow("1");
+    suite.execute("maker", &usei(500_000), ExecuteMsg::CreateSwap {
+        offered_nfts: vec![(NFT.to_string(), "1".to_string())],
+        requested_nfts: vec![(NFT.to_string(), "2".to_string())],
+        counterparty: None,
+    }).unwrap();
+
+    suite.escrow("2");
+    let response = suite.execute("taker", &[], ExecuteMsg::AcceptSwap { swap_id: 1 }).unwrap();
+    assert!(bank_sends(&response).is_empty());
+    assert_eq!(claimable(&suite, "taker"), vec![(PaymentAsset::default(), Uint128::new(500_000))]);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use cosmwasm_std::{Response, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetNftListingResponse, QueryMsg}, ContractError};
+
+use common::{attribute, usei, Suite, NFT};
+
+fn collection_bid(suite: &mut Suite, bidder: &str, prices: &[u128]) {
+    let total: u128 = prices.iter().sum();
+    suite.execute(bidder, &usei(total), ExecuteMsg::CollectionBid {
+        prices: prices.iter().copied().map(Uint128::new).collect(),
+        nft_contract_address: NFT.to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+}
+
+fn list_filling_collection_bid(suite: &mut Suite, lister: &str, token_id: &str, price: u128) -> Result<Response, ContractError> {
+    suite.execute(lister, &[], ExecuteMsg::List {
+        price: Uint128::new(price),
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        reserved_for: None,
+        reservation_expires_at: None,
+        on_reservation_expiry: None,
+        fill_collection_bid: Some(true),
+        escrowless: None,
+        cw20_contract_address: None,
+        denom: None,
+    })
+}
+
+#[test]
+fn listing_fills_the_highest_collection_bid() {
+    let mut suite = Suite::new();
+    collection_bid(&mut suite, "bob", &[1_000_000, 1_500_000]);
+    collection_bid(&mut suite, "carol", &[1_200_000]);
+    // bids of the lister are skipped
+    collection_bid(&mut suite, "alice", &[2_000_000]);
+
+    suite.escrow("1");
+    let response = list_filling_collection_bid(&mut suite, "alice", "1", 1_100_000).unwrap();
+    assert_eq!(attribute(&response, "bidder"), Some("bob"));
+    assert_eq!(attribute(&response, "filled_collection_bid_price"), Some("1500000"));
+
+    // the filled price is gone, carol has the best bid now
+    suite.escrow("2");
+    let response = list_filling_collection_bid(&mut suite, "alice", "2", 1_100_000).unwrap();
+    assert_eq!(attribute(&response, "bidder"), Some("carol"));
+    assert_eq!(attribute(&response, "filled_collection_bid_price"), Some("1200000"));
+}
+
+#[test]
+fn cancelled_collection_bid_is_not_filled() {
+    let mut suite = Suite::new();
+    collection_bid(&mut suite, "bob", &[1_500_000]);
+    collection_bid(&mut suite, "carol", &[1_200_000]);
+    suite.execute("bob", &[], ExecuteMsg::CancelCollectionBid {
+        nft_contract_address: NFT.to_string(),
+        price: Uint128::new(1_500_000),
+    }).unwrap();
+
+    suite.escrow("1");
+    let response = list_filling_collection_bid(&mut suite, "alice", "1", 1_000_000).unwrap();
+    assert_eq!(attribute(&response, "bidder"), Some("carol"));
+}
+
+#[test]
+fn listing_stays_listed_without_a_collection_bid_at_its_price() {
+    let mut suite = Suite::new();
+    collection_bid(&mut suite, "bob", &[900_000]);
+
+    suite.escrow("1");
+    let response = list_filling_collection_bid(&mut suite, "alice", "1", 1_000_000).unwrap();
+    assert_eq!(attribute(&response, "bidder"), None);
+
+    let listing: GetNftListingResponse = suite.query(QueryMsg::GetNftListing {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+    });
+    assert_eq!(listing.nft_listing.lister.as_str(), "alice");
+}
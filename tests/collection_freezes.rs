@@ -0,0 +1,138 @@
+mod common;
+
+use cosmwasm_std::{Binary, Response, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetCollectionFreezeResponse, QueryMsg, SignedOrder, SignedOrderSide}, ContractError};
+
+use common::{nft_transfers, usei, Suite, NFT};
+
+fn freeze(suite: &mut Suite, sender: &str, unfreeze_in: Option<u64>) -> Result<Response, ContractError> {
+    let unfreeze_at = unfreeze_in.map(|seconds| suite.env.block.time.plus_seconds(seconds));
+    suite.execute(sender, &[], ExecuteMsg::FreezeCollectionTrading { nft_contract_address: NFT.to_string(), unfreeze_at })
+}
+
+fn is_frozen(suite: &Suite) -> bool {
+    let response: GetCollectionFreezeResponse = suite.query(QueryMsg::GetCollectionFreeze { nft_contract_address: NFT.to_string() });
+    response.is_frozen
+}
+
+#[test]
+fn frozen_collection_cant_be_traded_until_unfrozen() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    freeze(&mut suite, "creator", None).unwrap();
+    assert!(is_frozen(&suite));
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionFrozen {  }));
+    suite.escrow("2");
+    let err = suite.list("lister", "2", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionFrozen {  }));
+
+    // cancels still work while frozen
+    let response = suite.execute("lister", &[], ExecuteMsg::CancelListing { nft_contract_address: NFT.to_string(), token_id: "1".to_string() }).unwrap();
+    assert_eq!(nft_transfers(&response), vec![("lister".to_string(), "1".to_string())]);
+
+    suite.execute("creator", &[], ExecuteMsg::UnfreezeCollectionTrading { nft_contract_address: NFT.to_string() }).unwrap();
+    assert!(!is_frozen(&suite));
+    suite.list("lister", "2", 1_000_000).unwrap();
+}
+
+#[test]
+fn scheduled_freeze_ends_at_unfreeze_time() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+    freeze(&mut suite, "creator", Some(3_600)).unwrap();
+
+    suite.escrow("1");
+    let err = suite.list("lister", "1", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionFrozen {  }));
+
+    suite.env.block.time = suite.env.block.time.plus_seconds(3_600);
+    assert!(!is_frozen(&suite));
+    suite.list("lister", "1", 1_000_000).unwrap();
+}
+
+#[test]
+fn only_the_collection_creator_can_freeze_trading() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+
+    let err = freeze(&mut suite, "someone", None).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+    let err = freeze(&mut suite, "creator", Some(0)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidUnfreezeTime {  }));
+}
+
+#[test]
+fn frozen_collection_rejects_selling_to_a_collection_bid() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+    suite.execute("bob", &usei(1_000_000), ExecuteMsg::CollectionBid {
+        prices: vec![Uint128::new(1_000_000)],
+        nft_contract_address: NFT.to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+    suite.mint("1", "alice");
+    suite.approve_marketplace("1");
+
+    freeze(&mut suite, "creator", None).unwrap();
+    let err = suite.execute("alice", &[], ExecuteMsg::SellToCollectionBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        bidder: "bob".to_string(),
+        price: Uint128::new(1_000_000),
+        referrer: None,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionFrozen {  }));
+}
+
+#[test]
+fn frozen_collection_rejects_signed_order_fills() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+    freeze(&mut suite, "creator", None).unwrap();
+
+    let order = SignedOrder {
+        maker: "maker".to_string(),
+        side: SignedOrderSide::Listing,
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        price: Uint128::new(1_000_000),
+        expires_at: suite.env.block.time.plus_seconds(3_600),
+        nonce: 0,
+    };
+    // the freeze is checked before the signature
+    let err = suite.execute("bob", &usei(1_000_000), ExecuteMsg::FillSignedOrder {
+        order,
+        signature: Binary::from(vec![0u8; 64]),
+        maker_pubkey: Binary::from(vec![0u8; 33]),
+        referrer: None,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionFrozen {  }));
+}
+
+#[test]
+fn bid_on_a_frozen_collection_cant_be_updated_into_a_listing() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+    suite.execute("bob", &usei(1_000_000), ExecuteMsg::Bid {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+    suite.escrow("1");
+    suite.list("alice", "1", 1_500_000).unwrap();
+
+    freeze(&mut suite, "creator", None).unwrap();
+    let err = suite.execute("bob", &usei(500_000), ExecuteMsg::UpdateBid {
+        new_price: Uint128::new(1_500_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionFrozen {  }));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use cosmwasm_std::Uint128;
+use tradesei::{msg::{ExecuteMsg, GetPaginatedVerifiedCollectionsResponse, QueryMsg}, state::CollectionInfo, ContractError};
+
+use common::{usei, Suite, ADMIN, NFT};
+
+fn verify_collection(suite: &mut Suite) {
+    suite.execute(ADMIN, &[], ExecuteMsg::SetCollectionInfo {
+        nft_contract_address: NFT.to_string(),
+        collection_info: CollectionInfo { verified: true, name: Some("Collection".to_string()), image: None, external_url: None },
+    }).unwrap();
+}
+
+#[test]
+fn curation_mode_only_allows_verified_collections() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateCurationMode { curation_mode: true }).unwrap();
+
+    suite.escrow("1");
+    let err = suite.list("lister", "1", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionNotVerified {  }));
+    let err = suite.execute("bidder", &usei(1_000_000), ExecuteMsg::Bid {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionNotVerified {  }));
+
+    verify_collection(&mut suite);
+    suite.list("lister", "1", 1_000_000).unwrap();
+}
+
+#[test]
+fn collections_are_tradable_without_curation_mode() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+}
+
+#[test]
+fn only_admin_can_verify_collections() {
+    let mut suite = Suite::new();
+    let err = suite.execute("lister", &[], ExecuteMsg::SetCollectionInfo {
+        nft_contract_address: NFT.to_string(),
+        collection_info: CollectionInfo { verified: true, name: None, image: None, external_url: None },
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+}
+
+#[test]
+fn removed_collections_are_no_longer_listed_as_verified() {
+    let mut suite = Suite::new();
+    verify_collection(&mut suite);
+    let response: GetPaginatedVerifiedCollectionsResponse = suite.query(QueryMsg::GetPaginatedVerifiedCollections { start_after: None, limit: None });
+    assert_eq!(response.collections.len(), 1);
+
+    suite.execute(ADMIN, &[], ExecuteMsg::RemoveCollectionInfo { nft_contract_address: NFT.to_string() }).unwrap();
+    let response: GetPaginatedVerifiedCollectionsResponse = suite.query(QueryMsg::GetPaginatedVerifiedCollections { start_after: None, limit: None });
+    assert!(response.collections.is_empty());
+
+    let err = suite.execute(ADMIN, &[], ExecuteMsg::RemoveCollectionInfo { nft_contract_address: NFT.to_string() }).unwrap_err();
+    assert!(matches!(err, ContractError::CollectionNotFound {  }));
+}
@@ -0,0 +1,293 @@
+#![allow(dead_code)]
+
+use std::{cell::RefCell, collections::{HashMap, HashSet}, marker::PhantomData, rc::Rc};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_json, testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage}, to_json_binary, Addr, Api, BankMsg, CanonicalAddr, Coin, ContractResult, CosmosMsg, Decimal, Deps, Env, Order, OwnedDeps, RecoverPubkeyError, Response, StdResult, Storage, SystemError, SystemResult, Uint128, VerificationError, WasmMsg, WasmQuery};
+use cw721::{Approval, ApprovalResponse, Cw721ExecuteMsg, Expiration, NftInfoResponse, OperatorResponse, OwnerOfResponse};
+use serde::de::DeserializeOwned;
+
+use tradesei::{contract, msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg}, ContractError};
+
+pub const ADMIN: &str = "admin";
+pub const NFT: &str = "nftcontract";
+
+#[cw_serde]
+pub struct Trait {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct Metadata {
+    pub attributes: Option<Vec<Trait>>,
+}
+
+// state of the mocked cw721 contracts, keyed by (nft contract address, token id)
+#[derive(Default)]
+pub struct Nfts {
+    pub owners: HashMap<(String, String), String>,
+    pub attributes: HashMap<(String, String), Vec<Trait>>,
+    pub approvals: HashSet<(String, String, String)>,
+    // (nft contract address, owner, operator)
+    pub operators: HashSet<(String, String, String)>,
+    // cw2981 royalty (recipient, rate) per nft contract address
+    pub royalties: HashMap<String, (String, Decimal)>,
+    // minter per nft contract address, also reported as the contract owner
+    pub minters: HashMap<String, String>,
+}
+
+#[cw_serde]
+enum Cw721QueryMsg {
+    OwnerOf { token_id: String, include_expired: Option<bool> },
+    Approval { token_id: String, spender: String, include_expired: Option<bool> },
+    Operator { owner: String, operator: String, include_expired: Option<bool> },
+    NftInfo { token_id: String },
+    Minter {},
+    Ownership {},
+    Extension { msg: Cw2981QueryMsg },
+}
+
+#[cw_serde]
+enum Cw2981QueryMsg {
+    CheckRoyalties {},
+    RoyaltyInfo { token_id: String, sale_price: Uint128 },
+}
+
+#[cw_serde]
+struct MinterResponse {
+    minter: Option<String>,
+}
+
+#[cw_serde]
+struct OwnershipResponse {
+    owner: Option<String>,
+}
+
+#[cw_serde]
+struct CheckRoyaltiesResponse {
+    royalty_payments: bool,
+}
+
+#[cw_serde]
+struct RoyaltyInfoResponse {
+    address: String,
+    royalty_amount: Uint128,
+}
+
+fn query_nfts(nfts: &Nfts, contract_addr: &str, msg: &[u8]) -> SystemResult<ContractResult<cosmwasm_std::Binary>> {
+    let Ok(msg) = from_json::<Cw721QueryMsg>(msg) else {
+        return SystemResult::Ok(ContractResult::Err("unsupported query".to_string()));
+    };
+    let key = |token_id: &str| (contract_addr.to_string(), token_id.to_string());
+    let response = match msg {
+        Cw721QueryMsg::OwnerOf { token_id, .. } => nfts.owners.get(&key(&token_id))
+            .map(|owner| to_json_binary(&OwnerOfResponse { owner: owner.clone(), approvals: vec![] })),
+        Cw721QueryMsg::Approval { token_id, spender, .. } => nfts.approvals.contains(&(contract_addr.to_string(), token_id, spender.clone()))
+            .then(|| to_json_binary(&ApprovalResponse { approval: Approval { spender, expires: Expiration::Never {} } })),
+        Cw721QueryMsg::Operator { owner, operator, .. } => nfts.operators.contains(&(contract_addr.to_string(), owner, operator.clone()))
+            .then(|| to_json_binary(&OperatorResponse { approval: Approval { spender: operator, expires: Expiration::Never {} } })),
+        Cw721QueryMsg::NftInfo { token_id } => nfts.owners.contains_key(&key(&token_id)).then(|| {
+            let attributes = nfts.attributes.get(&key(&token_id)).cloned();
+            to_json_binary(&NftInfoResponse { token_uri: None, extension: Some(Metadata { attributes }) })
+        }),
+        Cw721QueryMsg::Minter {} => Some(to_json_binary(&MinterResponse { minter: nfts.minters.get(contract_addr).cloned() })),
+        Cw721QueryMsg::Ownership {} => Some(to_json_binary(&OwnershipResponse { owner: nfts.minters.get(contract_addr).cloned() })),
+        // collections without a royalty fail the extension queries, same as cw721 contracts without cw2981
+        Cw721QueryMsg::Extension { msg } => nfts.royalties.get(contract_addr).map(|(address, rate)| match msg {
+            Cw2981QueryMsg::CheckRoyalties {} => to_json_binary(&CheckRoyaltiesResponse { royalty_payments: true }),
+            Cw2981QueryMsg::RoyaltyInfo { sale_price, .. } => to_json_binary(&RoyaltyInfoResponse { address: address.clone(), royalty_amount: sale_price * *rate }),
+        }),
+    };
+    match response {
+        Some(Ok(binary)) => SystemResult::Ok(ContractResult::Ok(binary)),
+        Some(Err(e)) => SystemResult::Err(SystemError::InvalidRequest { error: e.to_string(), request: Default::default() }),
+        None => SystemResult::Ok(ContractResult::Err("not found".to_string())),
+    }
+}
+
+// MockApi with chain style canonical addresses for registered key holders,
+// lets signature checks derive the address from the public key
+#[derive(Default)]
+pub struct TestApi {
+    pub mock_api: MockApi,
+    pub canonical_addresses: HashMap<String, Vec<u8>>,
+}
+
+impl Api for TestApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.mock_api.addr_validate(human)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        match self.canonical_addresses.get(human) {
+            Some(canonical_address) => Ok(CanonicalAddr::from(canonical_address.as_slice())),
+            None => self.mock_api.addr_canonicalize(human),
+        }
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        self.mock_api.addr_humanize(canonical)
+    }
+
+    fn secp256k1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, VerificationError> {
+        self.mock_api.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(&self, message_hash: &[u8], signature: &[u8], recovery_param: u8) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.mock_api.secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, VerificationError> {
+        self.mock_api.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(&self, messages: &[&[u8]], signatures: &[&[u8]], public_keys: &[&[u8]]) -> Result<bool, VerificationError> {
+        self.mock_api.ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.mock_api.debug(message)
+    }
+}
+
+pub struct Suite {
+    pub deps: OwnedDeps<MockStorage, TestApi, MockQuerier>,
+    pub env: Env,
+    pub nfts: Rc<RefCell<Nfts>>,
+}
+
+impl Suite {
+    pub fn new() -> Self {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: TestApi::default(),
+            querier: MockQuerier::default(),
+            custom_query_type: PhantomData,
+        };
+        let nfts = Rc::new(RefCell::new(Nfts::default()));
+        let querier_nfts = nfts.clone();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } => query_nfts(&querier_nfts.borrow(), contract_addr, msg.as_slice()),
+            _ => SystemResult::Err(SystemError::UnsupportedRequest { kind: "wasm".to_string() }),
+        });
+
+        let env = mock_env();
+        contract::instantiate(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), InstantiateMsg { admin: None }).unwrap();
+
+        Suite { deps, env, nfts }
+    }
+
+    pub fn contract_address(&self) -> String {
+        self.env.contract.address.to_string()
+    }
+
+    // a failed execution reverts its state changes, same as on chain
+    fn revert_on_error<T>(&mut self, execute: impl FnOnce(&mut Self) -> Result<T, ContractError>) -> Result<T, ContractError> {
+        let snapshot: Vec<_> = self.deps.storage.range(None, None, Order::Ascending).collect();
+        let result = execute(self);
+        if result.is_err() {
+            self.deps.storage = MockStorage::new();
+            for (key, value) in snapshot {
+                self.deps.storage.set(&key, &value);
+            }
+        }
+        result
+    }
+
+    pub fn execute(&mut self, sender: &str, funds: &[Coin], msg: ExecuteMsg) -> Result<Response, ContractError> {
+        self.revert_on_error(|suite| contract::execute(suite.deps.as_mut(), suite.env.clone(), mock_info(sender, funds), msg))
+    }
+
+    pub fn sudo(&mut self, msg: SudoMsg) -> Result<Response, ContractError> {
+        self.revert_on_error(|suite| contract::sudo(suite.deps.as_mut(), suite.env.clone(), msg))
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: QueryMsg) -> T {
+        let deps: Deps = self.deps.as_ref();
+        from_json(contract::query(deps, self.env.clone(), msg).unwrap()).unwrap()
+    }
+
+    pub fn mint(&mut self, token_id: &str, owner: &str) {
+        self.nfts.borrow_mut().owners.insert((NFT.to_string(), token_id.to_string()), owner.to_string());
+    }
+
+    pub fn mint_with_traits(&mut self, token_id: &str, owner: &str, traits: &[(&str, &str)]) {
+        self.mint(token_id, owner);
+        let traits = traits.iter().map(|(trait_type, value)| Trait { trait_type: trait_type.to_string(), value: value.to_string() }).collect();
+        self.nfts.borrow_mut().attributes.insert((NFT.to_string(), token_id.to_string()), traits);
+    }
+
+    // same as transferring the nft to the marketplace before executing
+    pub fn escrow(&mut self, token_id: &str) {
+        let contract_address = self.contract_address();
+        self.mint(token_id, contract_address.as_str());
+    }
+
+    pub fn approve_marketplace(&mut self, token_id: &str) {
+        let contract_address = self.contract_address();
+        self.nfts.borrow_mut().approvals.insert((NFT.to_string(), token_id.to_string(), contract_address));
+    }
+
+    pub fn approve_operator(&mut self, owner: &str, operator: &str) {
+        self.nfts.borrow_mut().operators.insert((NFT.to_string(), owner.to_string(), operator.to_string()));
+    }
+
+    pub fn set_minter(&mut self, minter: &str) {
+        self.nfts.borrow_mut().minters.insert(NFT.to_string(), minter.to_string());
+    }
+
+    pub fn set_royalty(&mut self, recipient: &str, rate: Decimal) {
+        self.nfts.borrow_mut().royalties.insert(NFT.to_string(), (recipient.to_string(), rate));
+    }
+
+    pub fn list(&mut self, lister: &str, token_id: &str, price: u128) -> Result<Response, ContractError> {
+        self.execute(lister, &[], ExecuteMsg::List {
+            price: Uint128::new(price),
+            nft_contract_address: NFT.to_string(),
+            token_id: token_id.to_string(),
+            reserved_for: None,
+            reservation_expires_at: None,
+            on_reservation_expiry: None,
+            fill_collection_bid: None,
+            escrowless: None,
+            cw20_contract_address: None,
+            denom: None,
+        })
+    }
+
+    pub fn buy_listing(&mut self, buyer: &str, token_id: &str, amount: u128) -> Result<Response, ContractError> {
+        self.execute(buyer, &usei(amount), ExecuteMsg::BuyListing {
+            nft_contract_address: NFT.to_string(),
+            token_id: token_id.to_string(),
+            referrer: None,
+        })
+    }
+}
+
+pub fn usei(amount: u128) -> Vec<Coin> {
+    cosmwasm_std::coins(amount, "usei")
+}
+
+// (recipient, amount) of every bank send in the response
+pub fn bank_sends(response: &Response) -> Vec<(String, u128)> {
+    response.messages.iter().filter_map(|sub_msg| match &sub_msg.msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => Some((to_address.clone(), amount.iter().map(|coin| coin.amount.u128()).sum())),
+        _ => None,
+    }).collect()
+}
+
+// (recipient, token id) of every nft transfer in the response
+pub fn nft_transfers(response: &Response) -> Vec<(String, String)> {
+    response.messages.iter().filter_map(|sub_msg| match &sub_msg.msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_json(msg) {
+            Ok(Cw721ExecuteMsg::TransferNft { recipient, token_id }) => Some((recipient, token_id)),
+            _ => None,
+        },
+        _ => None,
+    }).collect()
+}
+
+pub fn attribute<'a>(response: &'a Response, key: &str) -> Option<&'a str> {
+    response.attributes.iter().find(|attribute| attribute.key == key).map(|attribute| attribute.value.as_str())
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use cosmwasm_std::{from_json, to_json_binary, CosmosMsg, Response, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use tradesei::{msg::{ExecuteMsg, ReceiveMsg}, ContractError};
+
+use common::{attribute, Suite, ADMIN, NFT};
+
+const TOKEN: &str = "cw20token";
+
+// (recipient, amount) of every cw20 transfer in the response
+fn cw20_transfers(response: &Response) -> Vec<(String, u128)> {
+    response.messages.iter().filter_map(|sub_msg| match &sub_msg.msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) if contract_addr == TOKEN => match from_json(msg) {
+            Ok(Cw20ExecuteMsg::Transfer { recipient, amount }) => Some((recipient, amount.u128())),
+            _ => None,
+        },
+        _ => None,
+    }).collect()
+}
+
+fn list_for_cw20(suite: &mut Suite, token_id: &str) {
+    suite.escrow(token_id);
+    suite.execute("lister", &[], ExecuteMsg::List {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        reserved_for: None,
+        reservation_expires_at: None,
+        on_reservation_expiry: None,
+        fill_collection_bid: None,
+        escrowless: None,
+        cw20_contract_address: Some(TOKEN.to_string()),
+        denom: None,
+    }).unwrap();
+}
+
+fn receive(suite: &mut Suite, cw20: &str, sender: &str, amount: u128, msg: ReceiveMsg) -> Result<Response, ContractError> {
+    suite.execute(cw20, &[], ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_json_binary(&msg).unwrap(),
+    }))
+}
+
+fn buy_msg(token_id: &str) -> ReceiveMsg {
+    ReceiveMsg::BuyListing { nft_contract_address: NFT.to_string(), token_id: token_id.to_string(), referrer: None }
+}
+
+#[test]
+fn cw20_listing_is_bought_and_paid_out_in_the_cw20() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::AllowCw20 { contract_address: TOKEN.to_string() }).unwrap();
+    list_for_cw20(&mut suite, "1");
+
+    let response = receive(&mut suite, TOKEN, "buyer", 1_020_000, buy_msg("1")).unwrap();
+    assert_eq!(attribute(&response, "total_amount"), Some("1020000"));
+    let transfers = cw20_transfers(&response);
+    assert!(transfers.contains(&("lister".to_string(), 1_000_000)));
+    assert_eq!(transfers.iter().map(|(_, amount)| amount).sum::<u128>(), 1_020_000);
+}
+
+#[test]
+fn cw20_listing_cant_be_bought_with_native_funds() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::AllowCw20 { contract_address: TOKEN.to_string() }).unwrap();
+    list_for_cw20(&mut suite, "1");
+
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::PaymentAssetMismatch {  }));
+}
+
+#[test]
+fn tokens_of_a_cw20_that_is_not_allowlisted_are_rejected() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::AllowCw20 { contract_address: TOKEN.to_string() }).unwrap();
+    list_for_cw20(&mut suite, "1");
+
+    let err = receive(&mut suite, "othertoken", "buyer", 1_020_000, buy_msg("1")).unwrap_err();
+    assert!(matches!(err, ContractError::Cw20NotAllowed {  }));
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use cosmwasm_std::{coins, BankMsg, CosmosMsg, Response, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetPaginatedListingsResponse, QueryMsg}, ContractError};
+
+use common::{Suite, ADMIN, NFT};
+
+const USDC: &str = "ibc/usdc";
+
+fn list_in_denom(suite: &mut Suite, token_id: &str, denom: Option<&str>) -> Result<Response, ContractError> {
+    suite.escrow(token_id);
+    suite.execute("lister", &[], ExecuteMsg::List {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        reserved_for: None,
+        reservation_expires_at: None,
+        on_reservation_expiry: None,
+        fill_collection_bid: None,
+        escrowless: None,
+        denom: denom.map(str::to_string),
+        cw20_contract_address: None,
+    })
+}
+
+fn listings_in(suite: &Suite, denom: &str) -> Vec<String> {
+    let response: GetPaginatedListingsResponse = suite.query(QueryMsg::GetPaginatedListings {
+        nft_contract_address: NFT.to_string(),
+        start_after: None,
+        limit: None,
+        denom: Some(denom.to_string()),
+    });
+    response.listings.into_iter().map(|(token_id, _)| token_id).collect()
+}
+
+#[test]
+fn listing_in_an_allowlisted_denom_is_paid_out_in_it() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::AllowDenom { denom: USDC.to_string() }).unwrap();
+    list_in_denom(&mut suite, "1", Some(USDC)).unwrap();
+    list_in_denom(&mut suite, "2", None).unwrap();
+    assert_eq!(listings_in(&suite, USDC), vec!["1".to_string()]);
+    assert_eq!(listings_in(&suite, "usei"), vec!["2".to_string()]);
+
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+
+    let response = suite.execute("buyer", &coins(1_020_000, USDC), ExecuteMsg::BuyListing {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        referrer: None,
+    }).unwrap();
+    let lister_payout = response.messages.iter().find_map(|sub_msg| match &sub_msg.msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) if to_address == "lister" => Some(amount.clone()),
+        _ => None,
+    });
+    assert_eq!(lister_payout, Some(coins(1_000_000, USDC)));
+}
+
+#[test]
+fn listing_in_a_denom_that_is_not_allowlisted_is_rejected() {
+    let mut suite = Suite::new();
+    let err = list_in_denom(&mut suite, "1", Some(USDC)).unwrap_err();
+    assert!(matches!(err, ContractError::DenomNotAllowed {  }));
+}
+
+#[test]
+fn usei_cant_be_allowlisted() {
+    let mut suite = Suite::new();
+    let err = suite.execute(ADMIN, &[], ExecuteMsg::AllowDenom { denom: "usei".to_string() }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidDenom {  }));
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use cosmwasm_std::{Response, Uint128};
+use tradesei::{contract, msg::{ExecuteMsg, QueryMsg}, ContractError};
+
+use common::{nft_transfers, usei, Suite, NFT};
+
+fn list_escrowless(suite: &mut Suite, lister: &str, token_id: &str) -> Result<Response, ContractError> {
+    suite.execute(lister, &[], ExecuteMsg::List {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        reserved_for: None,
+        reservation_expires_at: None,
+        on_reservation_expiry: None,
+        fill_collection_bid: None,
+        escrowless: Some(true),
+        cw20_contract_address: None,
+        denom: None,
+    })
+}
+
+fn invalidate_msg(token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::InvalidateStaleListing { nft_contract_address: NFT.to_string(), token_id: token_id.to_string() }
+}
+
+#[test]
+fn escrowless_listing_is_bought_from_the_lister_wallet() {
+    let mut suite = Suite::new();
+    suite.mint("1", "lister");
+    suite.approve_marketplace("1");
+    list_escrowless(&mut suite, "lister", "1").unwrap();
+
+    let err = suite.execute("anyone", &[], invalidate_msg("1")).unwrap_err();
+    assert!(matches!(err, ContractError::ListingNotStale {  }));
+
+    let response = suite.buy_listing("buyer", "1", 1_020_000).unwrap();
+    assert_eq!(nft_transfers(&response), vec![("buyer".to_string(), "1".to_string())]);
+}
+
+#[test]
+fn escrowless_listing_needs_an_approval() {
+    let mut suite = Suite::new();
+    suite.mint("1", "lister");
+
+    let err = list_escrowless(&mut suite, "lister", "1").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotApproved {  }));
+
+    // an operator approval of every nft of the owner works too
+    let contract_address = suite.contract_address();
+    suite.approve_operator("lister", contract_address.as_str());
+    list_escrowless(&mut suite, "lister", "1").unwrap();
+}
+
+#[test]
+fn escrowless_listing_of_a_transferred_nft_is_stale() {
+    let mut suite = Suite::new();
+    suite.mint("1", "lister");
+    suite.approve_marketplace("1");
+    list_escrowless(&mut suite, "lister", "1").unwrap();
+
+    // the lister moved the nft elsewhere
+    suite.mint("1", "other");
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::StaleListing {  }));
+
+    suite.execute("anyone", &[], invalidate_msg("1")).unwrap();
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::NftListingNotFound {  }));
+}
+
+fn sell_to_bid(suite: &mut Suite, seller: &str, token_id: &str) -> Result<Response, ContractError> {
+    suite.execute(seller, &[], ExecuteMsg::SellToBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        bidder: "bidder".to_string(),
+        referrer: None,
+    })
+}
+
+fn sell_to_collection_bid(suite: &mut Suite, seller: &str, token_id: &str) -> Result<Response, ContractError> {
+    suite.execute(seller, &[], ExecuteMsg::SellToCollectionBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        bidder: "bidder".to_string(),
+        price: Uint128::new(1_000_000),
+        referrer: None,
+    })
+}
+
+#[test]
+fn only_the_lister_or_owner_can_sell_to_a_bid() {
+    let mut suite = Suite::new();
+    for token_id in ["1", "2"] {
+        suite.execute("bidder", &usei(1_000_000), ExecuteMsg::Bid {
+            price: Uint128::new(1_000_000),
+            nft_contract_address: NFT.to_string(),
+            token_id: token_id.to_string(),
+            use_balance: None,
+            denom: None,
+        }).unwrap();
+    }
+
+    // escrowed nft can only be sold by the lister of record, the sale replaces the listing
+    suite.escrow("1");
+    suite.list("lister", "1", 5_000_000).unwrap();
+    let err = sell_to_bid(&mut suite, "attacker", "1").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+    let response = sell_to_bid(&mut suite, "lister", "1").unwrap();
+    assert_eq!(nft_transfers(&response), vec![("bidder".to_string(), "1".to_string())]);
+    let listing_query = QueryMsg::GetNftListing { nft_contract_address: NFT.to_string(), token_id: "1".to_string() };
+    assert!(contract::query(suite.deps.as_ref(), suite.env.clone(), listing_query).is_err());
+
+    // wallet nft can only be sold by its owner
+    suite.mint("2", "owner");
+    suite.approve_marketplace("2");
+    let err = sell_to_bid(&mut suite, "attacker", "2").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+    let response = sell_to_bid(&mut suite, "owner", "2").unwrap();
+    assert_eq!(nft_transfers(&response), vec![("bidder".to_string(), "2".to_string())]);
+}
+
+#[test]
+fn only_the_lister_or_owner_can_sell_to_a_collection_bid() {
+    let mut suite = Suite::new();
+    suite.execute("bidder", &usei(2_000_000), ExecuteMsg::CollectionBid {
+        prices: vec![Uint128::new(1_000_000), Uint128::new(1_000_000)],
+        nft_contract_address: NFT.to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+
+    // nft moved to escrow without a listing has no seller of record
+    suite.escrow("1");
+    let err = sell_to_collection_bid(&mut suite, "attacker", "1").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+    suite.list("lister", "1", 5_000_000).unwrap();
+    let err = sell_to_collection_bid(&mut suite, "attacker", "1").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+    let response = sell_to_collection_bid(&mut suite, "lister", "1").unwrap();
+    assert_eq!(nft_transfers(&response), vec![("bidder".to_string(), "1".to_string())]);
+
+    suite.mint("2", "owner");
+    suite.approve_marketplace("2");
+    let err = sell_to_collection_bid(&mut suite, "attacker", "2").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+    let response = sell_to_collection_bid(&mut suite, "owner", "2").unwrap();
+    assert_eq!(nft_transfers(&response), vec![("bidder".to_string(), "2".to_string())]);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use cosmwasm_std::{Decimal, Uint128};
+use tradesei::{msg::ExecuteMsg, state::PLATFORM_FEE_RECEIVER, ContractError};
+
+use common::{attribute, bank_sends, usei, Suite, ADMIN, NFT};
+
+fn set_fee_override(suite: &mut Suite, sender: &str, fee: Decimal) -> Result<cosmwasm_std::Response, ContractError> {
+    suite.execute(sender, &[], ExecuteMsg::SetCollectionFeeOverride {
+        nft_contract_address: NFT.to_string(),
+        fee,
+        starts_at: None,
+        ends_at: None,
+    })
+}
+
+#[test]
+fn zero_fee_override_sells_without_zero_amount_payouts() {
+    let mut suite = Suite::new();
+    set_fee_override(&mut suite, ADMIN, Decimal::zero()).unwrap();
+
+    suite.escrow("1");
+    suite.list("seller", "1", 1_000_000).unwrap();
+    let response = suite.buy_listing("buyer", "1", 1_000_000).unwrap();
+
+    assert_eq!(bank_sends(&response), vec![("seller".to_string(), 1_000_000)]);
+}
+
+#[test]
+fn fee_override_above_max_fee_is_rejected() {
+    let mut suite = Suite::new();
+
+    let err = set_fee_override(&mut suite, ADMIN, Decimal::percent(11)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidCollectionFeeOverride {  }));
+
+    let err = set_fee_override(&mut suite, "user", Decimal::percent(1)).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+}
+
+#[test]
+fn fee_override_is_the_total_fee_of_the_sale() {
+    let mut suite = Suite::new();
+    set_fee_override(&mut suite, ADMIN, Decimal::percent(5)).unwrap();
+
+    // buyer takes the listing and pays the whole 5% on top of the price
+    suite.escrow("1");
+    suite.list("seller", "1", 1_000_000).unwrap();
+    let err = suite.buy_listing("buyer", "1", 1_049_999).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+    let response = suite.buy_listing("buyer", "1", 1_050_000).unwrap();
+    assert_eq!(attribute(&response, "seller_fee"), Some("0"));
+    assert_eq!(attribute(&response, "buyer_fee"), Some("50000"));
+    let sends = bank_sends(&response);
+    assert!(sends.contains(&(PLATFORM_FEE_RECEIVER.to_string(), 50_000)));
+    assert!(sends.contains(&("seller".to_string(), 1_000_000)));
+
+    // seller takes the bid and pays the whole 5% out of the price
+    suite.execute("bidder", &usei(1_000_000), ExecuteMsg::Bid {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "2".to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+    suite.mint("2", "seller");
+    suite.approve_marketplace("2");
+    let response = suite.execute("seller", &[], ExecuteMsg::SellToBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: "2".to_string(),
+        bidder: "bidder".to_string(),
+        referrer: None,
+    }).unwrap();
+    let sends = bank_sends(&response);
+    assert!(sends.contains(&(PLATFORM_FEE_RECEIVER.to_string(), 50_000)));
+    assert!(sends.contains(&("seller".to_string(), 950_000)));
+}
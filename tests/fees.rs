@@ -0,0 +1,107 @@
+mod common;
+
+use cosmwasm_std::{Decimal, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetEffectiveFeeResponse, QueryMsg}, state::{FeeSchedule, FeeTier}, ContractError};
+
+use common::{attribute, bank_sends, usei, Suite, ADMIN, NFT};
+
+fn fee_schedule() -> FeeSchedule {
+    FeeSchedule {
+        maker_fee: Decimal::percent(1),
+        taker_fee: Decimal::percent(2),
+        tiers: vec![FeeTier {
+            min_volume: Uint128::new(1_000_000),
+            maker_fee: Decimal::permille(5),
+            taker_fee: Decimal::percent(1),
+        }],
+    }
+}
+
+#[test]
+fn each_side_pays_its_own_rate_from_the_volume_before_the_sale() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateFeeSchedule { fee_schedule: fee_schedule() }).unwrap();
+
+    // the first sale pushes both accounts into the tier but is charged at the base rates
+    suite.escrow("1");
+    suite.list("alice", "1", 1_000_000).unwrap();
+    let response = suite.buy_listing("bob", "1", 1_020_000).unwrap();
+    assert_eq!(attribute(&response, "seller_fee"), Some("10000"));
+    assert_eq!(attribute(&response, "buyer_fee"), Some("20000"));
+    assert_eq!(attribute(&response, "total_amount"), Some("1020000"));
+    assert!(bank_sends(&response).contains(&("alice".to_string(), 990_000)));
+
+    let effective_fee: GetEffectiveFeeResponse = suite.query(QueryMsg::GetEffectiveFee { account: "bob".to_string() });
+    assert_eq!(effective_fee.maker_fee, Decimal::permille(5));
+    assert_eq!(effective_fee.taker_fee, Decimal::percent(1));
+
+    // the seller is still on the base maker rate, the buyer reached the taker tier
+    suite.escrow("2");
+    suite.list("carol", "2", 1_000_000).unwrap();
+    let response = suite.buy_listing("bob", "2", 1_010_000).unwrap();
+    assert_eq!(attribute(&response, "seller_fee"), Some("10000"));
+    assert_eq!(attribute(&response, "buyer_fee"), Some("10000"));
+}
+
+#[test]
+fn buyer_has_to_cover_its_taker_fee() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("alice", "1", 1_000_000).unwrap();
+
+    let err = suite.buy_listing("bob", "1", 1_000_000).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+}
+
+#[test]
+fn bid_fill_charges_both_sides_out_of_the_bid_price() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateFeeSchedule { fee_schedule: fee_schedule() }).unwrap();
+    suite.execute("bidder", &usei(1_000_000), ExecuteMsg::Bid {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+
+    // the seller takes the resting bid of the maker
+    suite.escrow("1");
+    suite.list("seller", "1", 5_000_000).unwrap();
+    let response = suite.execute("seller", &[], ExecuteMsg::SellToBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        bidder: "bidder".to_string(),
+        referrer: None,
+    }).unwrap();
+    assert_eq!(attribute(&response, "seller_fee"), Some("20000"));
+    assert_eq!(attribute(&response, "buyer_fee"), Some("10000"));
+    assert!(bank_sends(&response).contains(&("seller".to_string(), 970_000)));
+}
+
+#[test]
+fn fee_schedule_above_max_fee_is_rejected() {
+    let mut suite = Suite::new();
+    let fee_schedule = FeeSchedule { taker_fee: Decimal::percent(11), ..fee_schedule() };
+
+    let err = suite.execute(ADMIN, &[], ExecuteMsg::UpdateFeeSchedule { fee_schedule }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFeeSchedule {  }));
+}
+
+#[test]
+fn bundle_sale_counts_its_price_once_toward_volume() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.escrow("2");
+    suite.execute("alice", &[], ExecuteMsg::ListBundle {
+        items: vec![(NFT.to_string(), "1".to_string()), (NFT.to_string(), "2".to_string())],
+        price: Uint128::new(1_000_000),
+        royalty_rule: None,
+    }).unwrap();
+    suite.execute("bob", &usei(1_020_000), ExecuteMsg::BuyBundle { bundle_id: 1, referrer: None }).unwrap();
+
+    for account in ["alice", "bob"] {
+        let effective_fee: GetEffectiveFeeResponse = suite.query(QueryMsg::GetEffectiveFee { account: account.to_string() });
+        assert_eq!(effective_fee.volume, Uint128::new(1_000_000));
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use cosmwasm_std::{from_json, CosmosMsg, Response, WasmMsg};
+use tradesei::{msg::{ExecuteMsg, HookExecuteMsg, TradeHookAction}, ContractError};
+
+use common::{Suite, ADMIN, NFT};
+
+// (action, token id, buyer, seller)
+type HookCall = (TradeHookAction, String, Option<String>, String);
+
+// every hook notification with its gas limit
+fn hook_calls(response: &Response) -> Vec<(HookCall, Option<u64>)> {
+    response.messages.iter().filter_map(|sub_msg| match &sub_msg.msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) if contract_addr == "hook" => {
+            let HookExecuteMsg::TradeHook(trade_hook_msg) = from_json(msg).unwrap();
+            Some(((trade_hook_msg.action, trade_hook_msg.token_id, trade_hook_msg.buyer, trade_hook_msg.seller), sub_msg.gas_limit))
+        },
+        _ => None,
+    }).collect()
+}
+
+fn add_hook(suite: &mut Suite, sender: &str) -> Result<Response, ContractError> {
+    suite.execute(sender, &[], ExecuteMsg::AddHook { contract_address: "hook".to_string() })
+}
+
+#[test]
+fn hooks_are_notified_with_the_configured_gas_limit() {
+    let mut suite = Suite::new();
+    add_hook(&mut suite, ADMIN).unwrap();
+
+    suite.escrow("1");
+    let response = suite.list("lister", "1", 1_000_000).unwrap();
+    assert_eq!(hook_calls(&response), vec![((TradeHookAction::Listing, "1".to_string(), None, "lister".to_string()), Some(300_000))]);
+
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateHookGasLimit { hook_gas_limit: 50_000 }).unwrap();
+    let response = suite.buy_listing("buyer", "1", 1_020_000).unwrap();
+    assert_eq!(hook_calls(&response), vec![((TradeHookAction::Sale, "1".to_string(), Some("buyer".to_string()), "lister".to_string()), Some(50_000))]);
+}
+
+#[test]
+fn swaps_notify_every_traded_nft() {
+    let mut suite = Suite::new();
+    add_hook(&mut suite, ADMIN).unwrap();
+
+    suite.escrow("1");
+    suite.execute("maker", &[], ExecuteMsg::CreateSwap {
+        offered_nfts: vec![(NFT.to_string(), "1".to_string())],
+        requested_nfts: vec![(NFT.to_string(), "2".to_string())],
+        counterparty: None,
+    }).unwrap();
+
+    suite.escrow("2");
+    let response = suite.execute("taker", &[], ExecuteMsg::AcceptSwap { swap_id: 1 }).unwrap();
+    let actions: Vec<_> = hook_calls(&response).into_iter().map(|(call, _)| call).collect();
+    assert_eq!(actions, vec![
+        (TradeHookAction::Sale, "1".to_string(), Some("taker".to_string()), "maker".to_string()),
+        (TradeHookAction::Sale, "2".to_string(), Some("maker".to_string()), "taker".to_string()),
+    ]);
+}
+
+#[test]
+fn only_admin_manages_hooks() {
+    let mut suite = Suite::new();
+
+    let err = add_hook(&mut suite, "user").unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+
+    let err = suite.execute(ADMIN, &[], ExecuteMsg::UpdateHookGasLimit { hook_gas_limit: 0 }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidHookGasLimit {  }));
+}
@@ -0,0 +1,315 @@
+mod common;
+
+use std::str::FromStr;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Order, Storage, Uint128};
+use cw_storage_plus::Map;
+use tradesei::{contract, msg::{ExecuteMsg, GetMigrationProgressResponse, GetNftBundleResponse, GetNftCollectionBidResponse, GetNftListingResponse, GetNftTraitBidResponse, MigrateMsg, QueryMsg}, state::{BundleRoyaltyRule, MigrationProgress, NftItem, TraitFilter, MIGRATION_PROGRESS}, ContractError};
+
+use common::{attribute, usei, Suite, NFT};
+
+#[cw_serde]
+struct LegacyNftListing {
+    lister: Addr,
+    price: Decimal,
+    nft_contract_address: Addr,
+    token_id: String,
+    reservation: Option<String>,
+}
+
+#[cw_serde]
+struct LegacyNftCollectionBid {
+    bidder: Addr,
+    nft_contract_address: Addr,
+    bids_prices: Vec<Decimal>,
+}
+
+#[cw_serde]
+struct LegacyNftTraitBid {
+    id: u64,
+    bidder: Addr,
+    price: Decimal,
+    nft_contract_address: Addr,
+    traits: Vec<TraitFilter>,
+}
+
+#[cw_serde]
+struct LegacyNftBundle {
+    id: u64,
+    lister: Addr,
+    price: Decimal,
+    items: Vec<NftItem>,
+    royalty_rule: BundleRoyaltyRule,
+}
+
+const LEGACY_NFT_LISTINGS: Map<(&str, &str), LegacyNftListing> = Map::new("nft_listings");
+const LEGACY_NFT_COLLECTION_BIDS: Map<(&str, &str), LegacyNftCollectionBid> = Map::new("nft_collection_bids");
+const LEGACY_NFT_TRAIT_BIDS: Map<(&str, u64), LegacyNftTraitBid> = Map::new("nft_trait_bids");
+const LEGACY_NFT_BUNDLES: Map<u64, LegacyNftBundle> = Map::new("nft_bundles");
+
+#[test]
+fn decimal_prices_are_migrated_to_usei() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "0.1.0").unwrap();
+
+    let nft = Addr::unchecked(NFT);
+    let price = Decimal::percent(150);
+    LEGACY_NFT_LISTINGS.save(&mut suite.deps.storage, (NFT, "1"), &LegacyNftListing {
+        lister: Addr::unchecked("lister"),
+        price,
+        nft_contract_address: nft.clone(),
+        token_id: "1".to_string(),
+        reservation: None,
+    }).unwrap();
+    LEGACY_NFT_TRAIT_BIDS.save(&mut suite.deps.storage, (NFT, 1), &LegacyNftTraitBid {
+        id: 1,
+        bidder: Addr::unchecked("bidder"),
+        price,
+        nft_contract_address: nft.clone(),
+        traits: vec![TraitFilter { trait_type: "background".to_string(), value: "gold".to_string() }],
+    }).unwrap();
+    LEGACY_NFT_BUNDLES.save(&mut suite.deps.storage, 1, &LegacyNftBundle {
+        id: 1,
+        lister: Addr::unchecked("lister"),
+        price,
+        items: vec![NftItem { nft_contract_address: nft, token_id: "2".to_string() }],
+        royalty_rule: BundleRoyaltyRule::Equal,
+    }).unwrap();
+
+    contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: None }).unwrap();
+
+    let listing: GetNftListingResponse = suite.query(QueryMsg::GetNftListing { nft_contract_address: NFT.to_string(), token_id: "1".to_string() });
+    assert_eq!(listing.nft_listing.price, Uint128::new(1_500_000));
+    let trait_bid: GetNftTraitBidResponse = suite.query(QueryMsg::GetNftTraitBid { nft_contract_address: NFT.to_string(), trait_bid_id: 1 });
+    assert_eq!(trait_bid.nft_trait_bid.price, Uint128::new(1_500_000));
+    let bundle: GetNftBundleResponse = suite.query(QueryMsg::GetNftBundle { bundle_id: 1 });
+    assert_eq!(bundle.nft_bundle.price, Uint128::new(1_500_000));
+}
+
+#[test]
+fn migrated_collection_bids_are_indexed_by_price() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "0.1.0").unwrap();
+
+    LEGACY_NFT_COLLECTION_BIDS.save(&mut suite.deps.storage, (NFT, "bidder"), &LegacyNftCollectionBid {
+        bidder: Addr::unchecked("bidder"),
+        nft_contract_address: Addr::unchecked(NFT),
+        bids_prices: vec![Decimal::one(), Decimal::percent(150)],
+    }).unwrap();
+
+    contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: None }).unwrap();
+
+    suite.escrow("1");
+    let response = suite.execute("lister", &[], ExecuteMsg::List {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        reserved_for: None,
+        reservation_expires_at: None,
+        on_reservation_expiry: None,
+        fill_collection_bid: Some(true),
+        escrowless: None,
+        denom: None,
+        cw20_contract_address: None,
+    }).unwrap();
+    assert_eq!(attribute(&response, "filled_collection_bid_price"), Some("1500000"));
+}
+
+#[test]
+fn prices_are_taken_in_base_units() {
+    let mut suite = Suite::new();
+
+    let err = suite.execute("bidder", &usei(999_999), ExecuteMsg::TraitBid {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        traits: vec![TraitFilter { trait_type: "background".to_string(), value: "gold".to_string() }],
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+}
+
+fn save_legacy_listing(suite: &mut Suite, token_id: &str) {
+    LEGACY_NFT_LISTINGS.save(&mut suite.deps.storage, (NFT, token_id), &LegacyNftListing {
+        lister: Addr::unchecked("lister"),
+        price: Decimal::one(),
+        nft_contract_address: Addr::unchecked(NFT),
+        token_id: token_id.to_string(),
+        reservation: None,
+    }).unwrap();
+}
+
+#[test]
+fn paginated_migration_blocks_trading_until_finished() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "0.1.0").unwrap();
+    save_legacy_listing(&mut suite, "1");
+    save_legacy_listing(&mut suite, "2");
+
+    contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: Some(1) }).unwrap();
+    let progress: GetMigrationProgressResponse = suite.query(QueryMsg::GetMigrationProgress {});
+    assert!(progress.progress.is_some());
+
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::MigrationInProgress {  }));
+
+    suite.execute("anyone", &[], ExecuteMsg::ContinueMigration { limit: None }).unwrap();
+    let progress: GetMigrationProgressResponse = suite.query(QueryMsg::GetMigrationProgress {});
+    assert!(progress.progress.is_none());
+
+    let listing: GetNftListingResponse = suite.query(QueryMsg::GetNftListing { nft_contract_address: NFT.to_string(), token_id: "2".to_string() });
+    assert_eq!(listing.nft_listing.price, Uint128::new(1_000_000));
+    let err = suite.execute("anyone", &[], ExecuteMsg::ContinueMigration { limit: None }).unwrap_err();
+    assert!(matches!(err, ContractError::NoMigrationInProgress {  }));
+}
+
+#[test]
+fn resumed_migration_does_not_convert_entries_twice() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "0.1.0").unwrap();
+    save_legacy_listing(&mut suite, "1");
+    save_legacy_listing(&mut suite, "2");
+    LEGACY_NFT_COLLECTION_BIDS.save(&mut suite.deps.storage, (NFT, "bidder"), &LegacyNftCollectionBid {
+        bidder: Addr::unchecked("bidder"),
+        nft_contract_address: Addr::unchecked(NFT),
+        bids_prices: vec![Decimal::one()],
+    }).unwrap();
+
+    contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: Some(1) }).unwrap();
+    // the page was converted but its progress was lost, the listing step runs over listing 1 again
+    MIGRATION_PROGRESS.save(&mut suite.deps.storage, &MigrationProgress {
+        from_version: "0.1.0".to_string(),
+        step: 0,
+        start_after: None,
+        migrated: 0,
+        clearing_migrated_keys: false,
+    }).unwrap();
+    suite.execute("anyone", &[], ExecuteMsg::ContinueMigration { limit: None }).unwrap();
+
+    for token_id in ["1", "2"] {
+        let listing: GetNftListingResponse = suite.query(QueryMsg::GetNftListing { nft_contract_address: NFT.to_string(), token_id: token_id.to_string() });
+        assert_eq!(listing.nft_listing.price, Uint128::new(1_000_000));
+    }
+
+    suite.escrow("3");
+    let response = suite.execute("lister", &[], ExecuteMsg::List {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "3".to_string(),
+        reserved_for: None,
+        reservation_expires_at: None,
+        on_reservation_expiry: None,
+        fill_collection_bid: Some(true),
+        escrowless: None,
+        denom: None,
+        cw20_contract_address: None,
+    }).unwrap();
+    assert_eq!(attribute(&response, "filled_collection_bid_price"), Some("1000000"));
+}
+
+#[test]
+fn sub_usei_legacy_asks_are_rounded_and_escrowed_prices_truncated() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "0.1.0").unwrap();
+    LEGACY_NFT_LISTINGS.save(&mut suite.deps.storage, (NFT, "1"), &LegacyNftListing {
+        lister: Addr::unchecked("lister"),
+        price: Decimal::from_str("1.0000006").unwrap(),
+        nft_contract_address: Addr::unchecked(NFT),
+        token_id: "1".to_string(),
+        reservation: None,
+    }).unwrap();
+    save_legacy_listing(&mut suite, "2");
+    // 0.1.0 escrowed the truncated price, 1000000 usei for these bids
+    LEGACY_NFT_COLLECTION_BIDS.save(&mut suite.deps.storage, (NFT, "bidder"), &LegacyNftCollectionBid {
+        bidder: Addr::unchecked("bidder"),
+        nft_contract_address: Addr::unchecked(NFT),
+        bids_prices: vec![Decimal::from_str("1.0000005").unwrap()],
+    }).unwrap();
+    LEGACY_NFT_TRAIT_BIDS.save(&mut suite.deps.storage, (NFT, 1), &LegacyNftTraitBid {
+        id: 1,
+        bidder: Addr::unchecked("bidder"),
+        price: Decimal::from_str("1.0000009").unwrap(),
+        nft_contract_address: Addr::unchecked(NFT),
+        traits: vec![TraitFilter { trait_type: "background".to_string(), value: "gold".to_string() }],
+    }).unwrap();
+
+    let response = contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: None }).unwrap();
+
+    let listing: GetNftListingResponse = suite.query(QueryMsg::GetNftListing { nft_contract_address: NFT.to_string(), token_id: "1".to_string() });
+    assert_eq!(listing.nft_listing.price, Uint128::new(1_000_001));
+    let collection_bid: GetNftCollectionBidResponse = suite.query(QueryMsg::GetNftCollectionBid { nft_contract_address: NFT.to_string(), bidder: "bidder".to_string() });
+    assert_eq!(collection_bid.nft_collection_bid.bids_prices, vec![Uint128::new(1_000_000)]);
+    let trait_bid: GetNftTraitBidResponse = suite.query(QueryMsg::GetNftTraitBid { nft_contract_address: NFT.to_string(), trait_bid_id: 1 });
+    assert_eq!(trait_bid.nft_trait_bid.price, Uint128::new(1_000_000));
+
+    let rounded_prices = response.events
+        .iter()
+        .filter(|event| event.ty == "legacy_price_rounded")
+        .filter_map(|event| event.attributes.iter().find(|attribute| attribute.key == "price"))
+        .map(|attribute| attribute.value.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(rounded_prices, vec!["1000001", "1000000", "1000000"]);
+}
+
+#[test]
+fn migrate_resumes_an_unfinished_migration() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "0.1.0").unwrap();
+    save_legacy_listing(&mut suite, "1");
+    save_legacy_listing(&mut suite, "2");
+
+    contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: Some(1) }).unwrap();
+
+    // e.g. new code replacing a step that kept failing continues from the stored cursor
+    let response = contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: None }).unwrap();
+    assert_eq!(attribute(&response, "resumed_migration"), Some("true"));
+    assert_eq!(attribute(&response, "from_version"), Some("0.1.0"));
+    assert_eq!(attribute(&response, "migration_finished"), Some("true"));
+
+    for token_id in ["1", "2"] {
+        let listing: GetNftListingResponse = suite.query(QueryMsg::GetNftListing { nft_contract_address: NFT.to_string(), token_id: token_id.to_string() });
+        assert_eq!(listing.nft_listing.price, Uint128::new(1_000_000));
+    }
+    suite.buy_listing("buyer", "1", 1_020_000).unwrap();
+}
+
+#[test]
+fn recorded_keys_are_removed_once_the_migration_is_done() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "0.1.0").unwrap();
+    save_legacy_listing(&mut suite, "1");
+    save_legacy_listing(&mut suite, "2");
+    save_legacy_listing(&mut suite, "3");
+
+    let has_migrated_keys = |suite: &Suite| suite.deps.storage
+        .range(None, None, Order::Ascending)
+        .any(|(key, _)| key.windows(b"migrated_keys".len()).any(|window| window == b"migrated_keys"));
+
+    contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: Some(2) }).unwrap();
+    assert!(has_migrated_keys(&suite));
+
+    // the keys are removed a page at a time too
+    let mut finished = false;
+    while !finished {
+        let response = suite.execute("anyone", &[], ExecuteMsg::ContinueMigration { limit: Some(2) }).unwrap();
+        finished = attribute(&response, "migration_finished") == Some("true");
+    }
+    assert!(!has_migrated_keys(&suite));
+}
+
+#[test]
+fn migration_from_a_newer_version_is_rejected() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:tradesei", "99.0.0").unwrap();
+
+    let err = contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: None }).unwrap_err();
+    assert!(matches!(err, ContractError::MigrationDowngrade {  }));
+}
+
+#[test]
+fn migration_from_another_contract_is_rejected() {
+    let mut suite = Suite::new();
+    cw2::set_contract_version(&mut suite.deps.storage, "crates.io:other", "0.1.0").unwrap();
+
+    let err = contract::migrate(suite.deps.as_mut(), suite.env.clone(), MigrateMsg { admin: None, limit: None }).unwrap_err();
+    assert!(matches!(err, ContractError::MigrationContractMismatch {  }));
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use cosmwasm_std::{Decimal, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetReferralEarningsResponse, QueryMsg}, state::PLATFORM_FEE_RECEIVER, ContractError};
+
+use common::{attribute, bank_sends, usei, Suite, ADMIN, NFT};
+
+fn buy_with_referrer(suite: &mut Suite, buyer: &str, referrer: &str) -> Result<cosmwasm_std::Response, ContractError> {
+    suite.execute(buyer, &usei(1_020_000), ExecuteMsg::BuyListing {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        referrer: Some(referrer.to_string()),
+    })
+}
+
+#[test]
+fn referrer_earns_its_share_of_the_platform_fee() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateReferralFeeShare { referral_fee_share: Decimal::percent(50) }).unwrap();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let response = buy_with_referrer(&mut suite, "buyer", "frontend").unwrap();
+    assert_eq!(attribute(&response, "referral_fee"), Some("10000"));
+    let sends = bank_sends(&response);
+    assert!(sends.contains(&("frontend".to_string(), 10_000)));
+    assert!(sends.contains(&(PLATFORM_FEE_RECEIVER.to_string(), 10_000)));
+
+    let earnings: GetReferralEarningsResponse = suite.query(QueryMsg::GetReferralEarnings { referrer: "frontend".to_string() });
+    assert_eq!(earnings.earnings, vec![("usei".to_string(), Uint128::new(10_000))]);
+}
+
+#[test]
+fn buyer_cant_refer_itself() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let err = buy_with_referrer(&mut suite, "buyer", "buyer").unwrap_err();
+    assert!(matches!(err, ContractError::InvalidReferrer {  }));
+}
+
+#[test]
+fn referral_share_above_the_platform_fee_is_rejected() {
+    let mut suite = Suite::new();
+    let err = suite.execute(ADMIN, &[], ExecuteMsg::UpdateReferralFeeShare { referral_fee_share: Decimal::percent(110) }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidReferralFeeShare {  }));
+}
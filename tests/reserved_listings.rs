@@ -0,0 +1,80 @@
+mod common;
+
+use cosmwasm_std::{Response, Uint128};
+use tradesei::{msg::{ExecuteMsg, GetPaginatedReservedListingsResponse, QueryMsg}, state::ReservationExpiry, ContractError};
+
+use common::{nft_transfers, Suite, NFT};
+
+fn list_reserved(suite: &mut Suite, token_id: &str, expires_in: Option<u64>, on_reservation_expiry: Option<ReservationExpiry>) -> Result<Response, ContractError> {
+    suite.escrow(token_id);
+    let reservation_expires_at = expires_in.map(|seconds| suite.env.block.time.plus_seconds(seconds));
+    suite.execute("lister", &[], ExecuteMsg::List {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        reserved_for: Some("friend".to_string()),
+        reservation_expires_at,
+        on_reservation_expiry,
+        fill_collection_bid: None,
+        escrowless: None,
+        cw20_contract_address: None,
+        denom: None,
+    })
+}
+
+#[test]
+fn reserved_listing_can_only_be_bought_by_the_reserved_buyer() {
+    let mut suite = Suite::new();
+    list_reserved(&mut suite, "1", None, None).unwrap();
+
+    let reserved: GetPaginatedReservedListingsResponse = suite.query(QueryMsg::GetPaginatedReservedListings {
+        buyer: "friend".to_string(),
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(reserved.listings.len(), 1);
+
+    let err = suite.buy_listing("stranger", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::ListingReservedForAnotherBuyer {  }));
+
+    suite.buy_listing("friend", "1", 1_020_000).unwrap();
+    let reserved: GetPaginatedReservedListingsResponse = suite.query(QueryMsg::GetPaginatedReservedListings {
+        buyer: "friend".to_string(),
+        start_after: None,
+        limit: None,
+    });
+    assert!(reserved.listings.is_empty());
+}
+
+#[test]
+fn expired_reservation_turns_the_listing_public() {
+    let mut suite = Suite::new();
+    list_reserved(&mut suite, "1", Some(3_600), Some(ReservationExpiry::Public)).unwrap();
+
+    suite.env.block.time = suite.env.block.time.plus_seconds(3_600);
+    suite.buy_listing("stranger", "1", 1_020_000).unwrap();
+}
+
+#[test]
+fn expired_reservation_with_return_sends_the_nft_back() {
+    let mut suite = Suite::new();
+    list_reserved(&mut suite, "1", Some(3_600), Some(ReservationExpiry::Return)).unwrap();
+
+    let return_msg = ExecuteMsg::ReturnExpiredListing { nft_contract_address: NFT.to_string(), token_id: "1".to_string() };
+    let err = suite.execute("anyone", &[], return_msg.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::ListingReservationNotExpired {  }));
+
+    suite.env.block.time = suite.env.block.time.plus_seconds(3_600);
+    let err = suite.buy_listing("friend", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::ListingReservationExpired {  }));
+
+    let response = suite.execute("anyone", &[], return_msg).unwrap();
+    assert_eq!(nft_transfers(&response), vec![("lister".to_string(), "1".to_string())]);
+}
+
+#[test]
+fn reservation_expiry_in_the_past_is_rejected() {
+    let mut suite = Suite::new();
+    let err = list_reserved(&mut suite, "1", Some(0), None).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidReservationExpiry {  }));
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use cosmwasm_std::Decimal;
+use tradesei::{msg::ExecuteMsg, ContractError};
+
+use common::{attribute, bank_sends, Suite, NFT};
+
+fn set_royalties(suite: &mut Suite, sender: &str, recipients: &[(&str, u64)]) -> Result<cosmwasm_std::Response, ContractError> {
+    suite.execute(sender, &[], ExecuteMsg::SetCollectionRoyalties {
+        nft_contract_address: NFT.to_string(),
+        recipients: recipients.iter().map(|(address, percent)| (address.to_string(), Decimal::percent(*percent))).collect(),
+    })
+}
+
+#[test]
+fn registry_royalties_are_split_between_recipients() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+    set_royalties(&mut suite, "creator", &[("artist", 5), ("studio", 3)]).unwrap();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    // price, 2% taker fee and 8% royalties
+    let err = suite.buy_listing("buyer", "1", 1_099_999).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+
+    let response = suite.buy_listing("buyer", "1", 1_100_000).unwrap();
+    assert_eq!(attribute(&response, "total_amount"), Some("1100000"));
+    let sends = bank_sends(&response);
+    assert!(sends.contains(&("artist".to_string(), 50_000)));
+    assert!(sends.contains(&("studio".to_string(), 30_000)));
+    assert!(sends.contains(&("lister".to_string(), 1_000_000)));
+}
+
+#[test]
+fn removed_registry_royalties_fall_back_to_the_nft_contract() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+    set_royalties(&mut suite, "creator", &[("artist", 5)]).unwrap();
+    suite.execute("creator", &[], ExecuteMsg::RemoveCollectionRoyalties { nft_contract_address: NFT.to_string() }).unwrap();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    // the nft contract royalty applies again
+    suite.set_royalty("minter", Decimal::percent(4));
+    let response = suite.buy_listing("buyer", "1", 1_060_000).unwrap();
+    let sends = bank_sends(&response);
+    assert!(sends.contains(&("minter".to_string(), 40_000)));
+    assert!(!sends.iter().any(|(recipient, _)| recipient == "artist"));
+}
+
+#[test]
+fn only_the_collection_creator_can_set_royalties() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+
+    let err = set_royalties(&mut suite, "someone", &[("someone", 5)]).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+}
+
+#[test]
+fn royalties_above_25_percent_are_rejected() {
+    let mut suite = Suite::new();
+    suite.set_minter("creator");
+
+    let err = set_royalties(&mut suite, "creator", &[("artist", 20), ("studio", 6)]).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidRoyaltyRecipients {  }));
+}
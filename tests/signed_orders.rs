@@ -0,0 +1,129 @@
+mod common;
+
+use cosmwasm_std::{to_json_vec, Binary, Uint128};
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use tradesei::{msg::{ExecuteMsg, GetSignedOrderStatusResponse, QueryMsg, SignedOrder, SignedOrderSide, SignedOrderSignDoc}, ContractError};
+
+use common::{bank_sends, nft_transfers, usei, Suite, NFT};
+
+const MAKER: &str = "maker";
+
+// registers the maker address as derived from the key, same as a chain address
+fn maker_key(suite: &mut Suite) -> SigningKey {
+    let key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let pubkey = key.verifying_key().to_sec1_bytes();
+    let canonical_address = Ripemd160::digest(Sha256::digest(&pubkey)).to_vec();
+    suite.deps.api.canonical_addresses.insert(MAKER.to_string(), canonical_address);
+    key
+}
+
+fn listing_order(suite: &Suite, token_id: &str, price: u128) -> SignedOrder {
+    SignedOrder {
+        maker: MAKER.to_string(),
+        side: SignedOrderSide::Listing,
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        price: Uint128::new(price),
+        expires_at: suite.env.block.time.plus_seconds(3_600),
+        nonce: 0,
+    }
+}
+
+fn sign(key: &SigningKey, chain_id: &str, contract_address: &str, order: &SignedOrder) -> Binary {
+    let sign_doc = SignedOrderSignDoc {
+        chain_id: chain_id.to_string(),
+        contract_address: contract_address.to_string(),
+        order: order.clone(),
+    };
+    let order_hash = Sha256::digest(to_json_vec(&sign_doc).unwrap());
+    let signature: Signature = key.sign_prehash(&order_hash).unwrap();
+    Binary::from(signature.to_bytes().as_slice())
+}
+
+fn fill_msg(key: &SigningKey, order: SignedOrder, signature: Binary) -> ExecuteMsg {
+    ExecuteMsg::FillSignedOrder {
+        order,
+        signature,
+        maker_pubkey: Binary::from(key.verifying_key().to_sec1_bytes().as_ref()),
+        referrer: None,
+    }
+}
+
+#[test]
+fn signed_listing_is_filled_once() {
+    let mut suite = Suite::new();
+    let key = maker_key(&mut suite);
+    suite.mint("1", MAKER);
+    suite.approve_marketplace("1");
+
+    let order = listing_order(&suite, "1", 1_000_000);
+    let signature = sign(&key, &suite.env.block.chain_id, &suite.contract_address(), &order);
+
+    // taker pays the 2% taker fee on top of the price like for a listing, the maker fee is 0% by default
+    let err = suite.execute("bob", &usei(1_019_999), fill_msg(&key, order.clone(), signature.clone())).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFundsSent {  }));
+
+    let response = suite.execute("bob", &usei(1_030_000), fill_msg(&key, order.clone(), signature.clone())).unwrap();
+    let sends = bank_sends(&response);
+    assert!(sends.contains(&(MAKER.to_string(), 1_000_000)));
+    // the surplus goes back to the taker
+    assert!(sends.contains(&("bob".to_string(), 10_000)));
+
+    let status: GetSignedOrderStatusResponse = suite.query(QueryMsg::GetSignedOrderStatus { order: order.clone() });
+    assert!(status.cancelled_or_filled);
+
+    let err = suite.execute("carol", &usei(1_000_000), fill_msg(&key, order, signature)).unwrap_err();
+    assert!(matches!(err, ContractError::SignedOrderCancelledOrFilled {  }));
+}
+
+#[test]
+fn order_signed_for_another_chain_is_rejected() {
+    let mut suite = Suite::new();
+    let key = maker_key(&mut suite);
+    suite.mint("1", MAKER);
+    suite.approve_marketplace("1");
+
+    let order = listing_order(&suite, "1", 1_000_000);
+    let signature = sign(&key, "other-chain", &suite.contract_address(), &order);
+
+    let err = suite.execute("bob", &usei(1_000_000), fill_msg(&key, order, signature)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSignature {  }));
+}
+
+#[test]
+fn expired_order_is_rejected() {
+    let mut suite = Suite::new();
+    let key = maker_key(&mut suite);
+    suite.mint("1", MAKER);
+    suite.approve_marketplace("1");
+
+    let order = listing_order(&suite, "1", 1_000_000);
+    let signature = sign(&key, &suite.env.block.chain_id, &suite.contract_address(), &order);
+    suite.env.block.time = order.expires_at;
+
+    let err = suite.execute("bob", &usei(1_000_000), fill_msg(&key, order, signature)).unwrap_err();
+    assert!(matches!(err, ContractError::SignedOrderExpired {  }));
+}
+
+#[test]
+fn signed_offer_is_filled_only_by_the_owner_or_lister_of_the_nft() {
+    let mut suite = Suite::new();
+    let key = maker_key(&mut suite);
+    suite.execute(MAKER, &usei(2_000_000), ExecuteMsg::Deposit {}).unwrap();
+
+    // an escrowed nft that no order holds can't be sold by anyone
+    suite.escrow("1");
+    let order = SignedOrder { side: SignedOrderSide::Offer, ..listing_order(&suite, "1", 1_000_000) };
+    let signature = sign(&key, &suite.env.block.chain_id, &suite.contract_address(), &order);
+    let err = suite.execute("bob", &[], fill_msg(&key, order, signature)).unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+
+    suite.mint("2", "bob");
+    suite.approve_marketplace("2");
+    let order = SignedOrder { side: SignedOrderSide::Offer, ..listing_order(&suite, "2", 1_000_000) };
+    let signature = sign(&key, &suite.env.block.chain_id, &suite.contract_address(), &order);
+    let response = suite.execute("bob", &[], fill_msg(&key, order, signature)).unwrap();
+    assert_eq!(nft_transfers(&response), vec![(MAKER.to_string(), "2".to_string())]);
+}
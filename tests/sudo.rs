@@ -0,0 +1,93 @@
+mod common;
+
+use cosmwasm_std::{Decimal, Uint128};
+use tradesei::{msg::{ExecuteMsg, SudoMsg}, state::FeeSchedule, ContractError};
+
+use common::{attribute, bank_sends, nft_transfers, usei, Suite, NFT};
+
+#[test]
+fn pause_blocks_execution_until_unpaused() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    suite.sudo(SudoMsg::Pause {}).unwrap();
+    let err = suite.buy_listing("buyer", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::Paused {  }));
+
+    suite.sudo(SudoMsg::Unpause {}).unwrap();
+    suite.buy_listing("buyer", "1", 1_020_000).unwrap();
+}
+
+#[test]
+fn governance_fee_schedule_applies_to_sales() {
+    let mut suite = Suite::new();
+    let fee_schedule = FeeSchedule { maker_fee: Decimal::percent(1), taker_fee: Decimal::percent(3), tiers: vec![] };
+    suite.sudo(SudoMsg::UpdateFeeSchedule { fee_schedule }).unwrap();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let response = suite.buy_listing("buyer", "1", 1_030_000).unwrap();
+    assert_eq!(attribute(&response, "seller_fee"), Some("10000"));
+    assert_eq!(attribute(&response, "buyer_fee"), Some("30000"));
+
+    let fee_schedule = FeeSchedule { maker_fee: Decimal::zero(), taker_fee: Decimal::percent(11), tiers: vec![] };
+    let err = suite.sudo(SudoMsg::UpdateFeeSchedule { fee_schedule }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFeeSchedule {  }));
+}
+
+#[test]
+fn force_return_sends_escrowed_assets_back() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+    suite.execute("bidder", &usei(500_000), ExecuteMsg::Bid {
+        price: Uint128::new(500_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "2".to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+
+    let response = suite.sudo(SudoMsg::ForceReturnListing { nft_contract_address: NFT.to_string(), token_id: "1".to_string() }).unwrap();
+    assert_eq!(nft_transfers(&response), vec![("lister".to_string(), "1".to_string())]);
+
+    let response = suite.sudo(SudoMsg::ForceReturnBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: "2".to_string(),
+        bidder: "bidder".to_string(),
+    }).unwrap();
+    assert_eq!(bank_sends(&response), vec![("bidder".to_string(), 500_000)]);
+
+    let err = suite.sudo(SudoMsg::ForceReturnListing { nft_contract_address: NFT.to_string(), token_id: "1".to_string() }).unwrap_err();
+    assert!(matches!(err, ContractError::NftListingNotFound {  }));
+}
+
+#[test]
+fn force_returned_collection_bids_are_no_longer_filled() {
+    let mut suite = Suite::new();
+    suite.execute("bidder", &usei(1_000_000), ExecuteMsg::CollectionBid {
+        prices: vec![Uint128::new(1_000_000)],
+        nft_contract_address: NFT.to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+
+    let response = suite.sudo(SudoMsg::ForceReturnCollectionBids { nft_contract_address: NFT.to_string(), bidder: "bidder".to_string() }).unwrap();
+    assert_eq!(bank_sends(&response), vec![("bidder".to_string(), 1_000_000)]);
+
+    suite.escrow("1");
+    let response = suite.execute("lister", &[], ExecuteMsg::List {
+        price: Uint128::new(900_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        reserved_for: None,
+        reservation_expires_at: None,
+        on_reservation_expiry: None,
+        fill_collection_bid: Some(true),
+        escrowless: None,
+        cw20_contract_address: None,
+        denom: None,
+    }).unwrap();
+    assert_eq!(attribute(&response, "bidder"), None);
+}
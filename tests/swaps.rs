@@ -0,0 +1,66 @@
+mod common;
+
+use tradesei::{msg::ExecuteMsg, ContractError};
+
+use common::{bank_sends, nft_transfers, usei, Suite, NFT};
+
+fn items(token_ids: &[&str]) -> Vec<(String, String)> {
+    token_ids.iter().map(|token_id| (NFT.to_string(), token_id.to_string())).collect()
+}
+
+#[test]
+fn accepted_swap_exchanges_the_nfts_and_offered_sei() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.execute("maker", &usei(500_000), ExecuteMsg::CreateSwap {
+        offered_nfts: items(&["1"]),
+        requested_nfts: items(&["2", "3"]),
+        counterparty: Some("taker".to_string()),
+    }).unwrap();
+
+    suite.escrow("2");
+    suite.escrow("3");
+    let err = suite.execute("other", &[], ExecuteMsg::AcceptSwap { swap_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+
+    let response = suite.execute("taker", &[], ExecuteMsg::AcceptSwap { swap_id: 1 }).unwrap();
+    assert_eq!(nft_transfers(&response), vec![
+        ("maker".to_string(), "2".to_string()),
+        ("maker".to_string(), "3".to_string()),
+        ("taker".to_string(), "1".to_string()),
+    ]);
+    assert_eq!(bank_sends(&response), vec![("taker".to_string(), 500_000)]);
+
+    let err = suite.execute("taker", &[], ExecuteMsg::AcceptSwap { swap_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::NftSwapNotFound {  }));
+}
+
+#[test]
+fn swap_with_duplicate_items_is_rejected() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+
+    let err = suite.execute("maker", &[], ExecuteMsg::CreateSwap {
+        offered_nfts: items(&["1", "1"]),
+        requested_nfts: items(&["2"]),
+        counterparty: None,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::DuplicateNftItems {  }));
+}
+
+#[test]
+fn only_the_maker_can_cancel_a_swap() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.execute("maker", &[], ExecuteMsg::CreateSwap {
+        offered_nfts: items(&["1"]),
+        requested_nfts: items(&["2"]),
+        counterparty: None,
+    }).unwrap();
+
+    let err = suite.execute("taker", &[], ExecuteMsg::CancelSwap { swap_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {  }));
+
+    let response = suite.execute("maker", &[], ExecuteMsg::CancelSwap { swap_id: 1 }).unwrap();
+    assert_eq!(nft_transfers(&response), vec![("maker".to_string(), "1".to_string())]);
+}
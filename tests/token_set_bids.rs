@@ -0,0 +1,86 @@
+mod common;
+
+use cosmwasm_std::{Binary, Response, Uint128};
+use sha2::{Digest, Sha256};
+use tradesei::{msg::ExecuteMsg, ContractError};
+
+use common::{attribute, bank_sends, usei, Suite, NFT};
+
+fn leaf(token_id: &str) -> [u8; 32] {
+    Sha256::digest(token_id.as_bytes()).into()
+}
+
+// root of the two token set {"1", "2"}
+fn merkle_root() -> Binary {
+    let (first, second) = if leaf("1") <= leaf("2") { (leaf("1"), leaf("2")) } else { (leaf("2"), leaf("1")) };
+    let mut hasher = Sha256::new();
+    hasher.update(first);
+    hasher.update(second);
+    Binary::from(hasher.finalize().to_vec())
+}
+
+fn token_set_bid(suite: &mut Suite, bidder: &str) -> Result<Response, ContractError> {
+    suite.execute(bidder, &usei(1_000_000), ExecuteMsg::TokenSetBid {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        merkle_root: merkle_root(),
+    })
+}
+
+fn sell_to_token_set_bid(suite: &mut Suite, seller: &str, token_id: &str, sibling: &str) -> Result<Response, ContractError> {
+    suite.execute(seller, &[], ExecuteMsg::SellToTokenSetBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        token_set_bid_id: 1,
+        proof: vec![Binary::from(leaf(sibling).to_vec())],
+        referrer: None,
+    })
+}
+
+#[test]
+fn escrowed_nft_in_the_set_is_sold_to_token_set_bid() {
+    let mut suite = Suite::new();
+    token_set_bid(&mut suite, "bidder").unwrap();
+
+    suite.escrow("1");
+    suite.list("seller", "1", 5_000_000).unwrap();
+    let response = sell_to_token_set_bid(&mut suite, "seller", "1", "2").unwrap();
+
+    assert_eq!(attribute(&response, "bidder"), Some("bidder"));
+    assert!(bank_sends(&response).contains(&("seller".to_string(), 980_000)));
+}
+
+#[test]
+fn nft_outside_the_set_is_rejected() {
+    let mut suite = Suite::new();
+    token_set_bid(&mut suite, "bidder").unwrap();
+
+    suite.escrow("3");
+    let err = sell_to_token_set_bid(&mut suite, "seller", "3", "2").unwrap_err();
+    assert!(matches!(err, ContractError::InvalidMerkleProof {  }));
+}
+
+#[test]
+fn listed_nft_of_another_user_cant_be_sold_to_token_set_bid() {
+    let mut suite = Suite::new();
+    token_set_bid(&mut suite, "bidder").unwrap();
+
+    suite.escrow("1");
+    suite.list("lister", "1", 5_000_000).unwrap();
+
+    let err = sell_to_token_set_bid(&mut suite, "attacker", "1", "2").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+}
+
+#[test]
+fn wallet_nft_has_to_be_owned_by_the_seller() {
+    let mut suite = Suite::new();
+    token_set_bid(&mut suite, "bidder").unwrap();
+
+    suite.mint("1", "owner");
+    suite.approve_marketplace("1");
+    let err = sell_to_token_set_bid(&mut suite, "attacker", "1", "2").unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+
+    sell_to_token_set_bid(&mut suite, "owner", "1", "2").unwrap();
+}
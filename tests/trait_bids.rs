@@ -0,0 +1,83 @@
+mod common;
+
+use cosmwasm_std::{CosmosMsg, Response, Uint128, WasmMsg};
+use tradesei::{msg::{ExecuteMsg, GetPaginatedTraitBidsResponse, QueryMsg}, state::TraitFilter, ContractError};
+
+use common::{attribute, bank_sends, usei, Suite, NFT};
+
+fn trait_bid(suite: &mut Suite, bidder: &str, price: u128, amount: u128) -> Result<Response, ContractError> {
+    suite.execute(bidder, &usei(amount), ExecuteMsg::TraitBid {
+        price: Uint128::new(price),
+        nft_contract_address: NFT.to_string(),
+        traits: vec![TraitFilter { trait_type: "background".to_string(), value: "gold".to_string() }],
+    })
+}
+
+fn sell_to_trait_bid(suite: &mut Suite, seller: &str, token_id: &str, trait_bid_id: u64) -> Result<Response, ContractError> {
+    suite.execute(seller, &[], ExecuteMsg::SellToTraitBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: token_id.to_string(),
+        trait_bid_id,
+        referrer: None,
+    })
+}
+
+#[test]
+fn escrowed_nft_with_matching_traits_is_sold_to_trait_bid() {
+    let mut suite = Suite::new();
+    trait_bid(&mut suite, "bidder", 1_000_000, 1_000_000).unwrap();
+
+    suite.mint_with_traits("1", "seller", &[("background", "gold"), ("eyes", "red")]);
+    suite.escrow("1");
+    suite.list("seller", "1", 5_000_000).unwrap();
+    let response = sell_to_trait_bid(&mut suite, "seller", "1", 1).unwrap();
+
+    assert_eq!(attribute(&response, "bidder"), Some("bidder"));
+    assert!(response.messages.iter().any(|sub_msg| matches!(&sub_msg.msg, CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == NFT)));
+    assert!(bank_sends(&response).contains(&("seller".to_string(), 980_000)));
+
+    let response: GetPaginatedTraitBidsResponse = suite.query(QueryMsg::GetPaginatedTraitBids { nft_contract_address: NFT.to_string(), start_after: None, limit: None });
+    assert!(response.trait_bids.is_empty());
+}
+
+#[test]
+fn nft_without_the_traits_is_rejected() {
+    let mut suite = Suite::new();
+    trait_bid(&mut suite, "bidder", 1_000_000, 1_000_000).unwrap();
+
+    suite.mint_with_traits("1", "seller", &[("background", "blue")]);
+    suite.escrow("1");
+    suite.list("seller", "1", 5_000_000).unwrap();
+    let err = sell_to_trait_bid(&mut suite, "seller", "1", 1).unwrap_err();
+    assert!(matches!(err, ContractError::NftTraitsMismatch {  }));
+
+    let response: GetPaginatedTraitBidsResponse = suite.query(QueryMsg::GetPaginatedTraitBids { nft_contract_address: NFT.to_string(), start_after: None, limit: None });
+    assert_eq!(response.trait_bids.len(), 1);
+}
+
+#[test]
+fn listed_nft_of_another_user_cant_be_sold_to_trait_bid() {
+    let mut suite = Suite::new();
+    trait_bid(&mut suite, "bidder", 1_000_000, 1_000_000).unwrap();
+
+    suite.mint_with_traits("1", "lister", &[("background", "gold")]);
+    suite.escrow("1");
+    suite.list("lister", "1", 5_000_000).unwrap();
+
+    let err = sell_to_trait_bid(&mut suite, "attacker", "1", 1).unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+}
+
+#[test]
+fn wallet_nft_has_to_be_owned_by_the_seller() {
+    let mut suite = Suite::new();
+    trait_bid(&mut suite, "bidder", 1_000_000, 1_000_000).unwrap();
+
+    suite.mint_with_traits("1", "owner", &[("background", "gold")]);
+    suite.approve_marketplace("1");
+    let err = sell_to_trait_bid(&mut suite, "attacker", "1", 1).unwrap_err();
+    assert!(matches!(err, ContractError::NftNotOwnedBySeller {  }));
+
+    suite.approve_operator("owner", "operator");
+    sell_to_trait_bid(&mut suite, "operator", "1", 1).unwrap();
+}
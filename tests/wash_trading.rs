@@ -0,0 +1,79 @@
+mod common;
+
+use cosmwasm_std::Uint128;
+use tradesei::{msg::ExecuteMsg, ContractError};
+
+use common::{usei, Suite, ADMIN, NFT};
+
+#[test]
+fn lister_cant_buy_its_own_listing() {
+    let mut suite = Suite::new();
+    suite.escrow("1");
+    suite.list("lister", "1", 1_000_000).unwrap();
+
+    let err = suite.buy_listing("lister", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::SelfTrade {  }));
+}
+
+#[test]
+fn nft_can_be_sold_back_once_the_resale_cooldown_passed() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateResaleCooldown { resale_cooldown_seconds: 3_600 }).unwrap();
+
+    suite.escrow("1");
+    suite.list("alice", "1", 1_000_000).unwrap();
+    suite.buy_listing("bob", "1", 1_020_000).unwrap();
+
+    suite.list("bob", "1", 1_000_000).unwrap();
+    let err = suite.buy_listing("alice", "1", 1_020_000).unwrap_err();
+    assert!(matches!(err, ContractError::ResaleCooldownActive {  }));
+
+    suite.env.block.time = suite.env.block.time.plus_seconds(3_600);
+    suite.buy_listing("alice", "1", 1_020_000).unwrap();
+}
+
+#[test]
+fn bidder_cant_sell_into_its_own_bid() {
+    let mut suite = Suite::new();
+    suite.execute("alice", &usei(1_000_000), ExecuteMsg::Bid {
+        price: Uint128::new(1_000_000),
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        use_balance: None,
+        denom: None,
+    }).unwrap();
+
+    suite.escrow("1");
+    suite.list("alice", "1", 5_000_000).unwrap();
+    let err = suite.execute("alice", &[], ExecuteMsg::SellToBid {
+        nft_contract_address: NFT.to_string(),
+        token_id: "1".to_string(),
+        bidder: "alice".to_string(),
+        referrer: None,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::SelfTrade {  }));
+}
+
+#[test]
+fn swap_back_within_the_resale_cooldown_is_rejected() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &[], ExecuteMsg::UpdateResaleCooldown { resale_cooldown_seconds: 3_600 }).unwrap();
+
+    suite.escrow("1");
+    suite.list("alice", "1", 1_000_000).unwrap();
+    suite.buy_listing("bob", "1", 1_020_000).unwrap();
+
+    // bob hands the nft back to alice for another nft
+    suite.escrow("2");
+    suite.escrow("1");
+    suite.execute("alice", &[], ExecuteMsg::CreateSwap {
+        offered_nfts: vec![(NFT.to_string(), "2".to_string())],
+        requested_nfts: vec![(NFT.to_string(), "1".to_string())],
+        counterparty: Some("bob".to_string()),
+    }).unwrap();
+    let err = suite.execute("bob", &[], ExecuteMsg::AcceptSwap { swap_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::ResaleCooldownActive {  }));
+
+    suite.env.block.time = suite.env.block.time.plus_seconds(3_600);
+    suite.execute("bob", &[], ExecuteMsg::AcceptSwap { swap_id: 1 }).unwrap();
+}